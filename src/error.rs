@@ -0,0 +1,130 @@
+use std::{fmt::Display, io, path::PathBuf};
+
+/// The errors that can occure while searching for steam, prefixes and reading their files
+#[derive(Debug)]
+pub enum ProtonFinderError {
+    /// `$STEAM_DIR` is set, but does not point to a steam root
+    InvalidEnvPath(PathBuf),
+    /// A custom root passed to the `SteamLocator` is not a steam root
+    InvalidRootPath(PathBuf),
+    /// No steam install was found
+    NoSteamRoot,
+    /// Steam was found, but none of the steam roots contain a prefix for this game
+    PrefixNotFound(u32),
+    /// The registry key (or value within it) does not exist
+    RegistryKeyMissing(String),
+    /// The wineserver of this prefix is running, so changes to the registry files would be overwritten
+    WineserverRunning(PathBuf),
+    /// The character is not a drive letter (a-z)
+    InvalidDriveLetter(char),
+    /// The drive letter is already used within the prefix
+    DriveInUse(char),
+    /// The drive can not be removed (it is `C:`, or not a link to a folder)
+    DriveNotRemovable(char),
+    /// The target of a new drive is not a folder
+    InvalidDriveTarget(PathBuf),
+    /// A file could not be parsed, line is 1 based
+    Parse { path: Option<PathBuf>, line: usize, reason: String },
+    /// Reading or writing a file failed
+    Io(io::Error),
+    /// This platform is neither Linux nor Windows
+    UnsupportedPlatform
+}
+
+impl Display for ProtonFinderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProtonFinderError::InvalidEnvPath(path) => write!(f, "$STEAM_DIR is set to \"{}\", which is not a steam install", path.display()),
+            ProtonFinderError::InvalidRootPath(path) => write!(f, "\"{}\" is not a steam install", path.display()),
+            ProtonFinderError::NoSteamRoot => write!(f, "no steam install found"),
+            ProtonFinderError::PrefixNotFound(game_id) => write!(f, "no prefix found for game {}, the game needs to be installed and launched once", game_id),
+            ProtonFinderError::RegistryKeyMissing(key) => write!(f, "registry key \"{}\" not found", key),
+            ProtonFinderError::WineserverRunning(path) => write!(f, "the wineserver for {} is running, close the game first", path.display()),
+            ProtonFinderError::InvalidDriveLetter(letter) => write!(f, "{} is not a drive letter", letter),
+            ProtonFinderError::DriveInUse(letter) => write!(f, "drive {}: is already in use", letter.to_ascii_uppercase()),
+            ProtonFinderError::DriveNotRemovable(letter) => write!(f, "drive {}: can not be removed", letter.to_ascii_uppercase()),
+            ProtonFinderError::InvalidDriveTarget(path) => write!(f, "\"{}\" is not a folder, so it can not be a drive", path.display()),
+            ProtonFinderError::Parse { path: Some(path), line, reason } => write!(f, "failed to parse {} at line {}: {}", path.display(), line, reason),
+            ProtonFinderError::Parse { path: None, line, reason } => write!(f, "failed to parse at line {}: {}", line, reason),
+            ProtonFinderError::Io(err) => write!(f, "io error: {}", err),
+            ProtonFinderError::UnsupportedPlatform => write!(f, "unsupported platform")
+        }
+    }
+}
+
+impl std::error::Error for ProtonFinderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ProtonFinderError::Io(err) => Some(err),
+            _ => None
+        }
+    }
+}
+
+impl From<io::Error> for ProtonFinderError {
+    fn from(value: io::Error) -> Self {
+        ProtonFinderError::Io(value)
+    }
+}
+
+/// The outcome of a search.
+///
+/// `result` is the value found, or the reason nothing was found.
+/// `warnings` are the problems that came up during the search, but did not stop it
+/// (like an invalid `$STEAM_DIR`, after which the remaining locations are still searched).
+#[derive(Debug)]
+pub struct SearchResult<T> {
+    pub result: Result<T, ProtonFinderError>,
+    pub warnings: Vec<ProtonFinderError>
+}
+
+impl<T> SearchResult<T> {
+    /// Returns the found value, discarding the error and warnings
+    pub fn ok(self) -> Option<T> {
+        self.result.ok()
+    }
+
+    /// Returns the result, discarding the warnings
+    pub fn into_result(self) -> Result<T, ProtonFinderError> {
+        self.result
+    }
+
+    pub fn has_warnings(&self) -> bool {
+        !self.warnings.is_empty()
+    }
+
+    /// Maps the found value, keeping the warnings
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> SearchResult<U> {
+        SearchResult { result: self.result.map(f), warnings: self.warnings }
+    }
+
+    /// If an invalid `$STEAM_DIR` was reported
+    fn invalid_env(&self) -> bool {
+        self.warnings.iter().any(|warn| matches!(warn, ProtonFinderError::InvalidEnvPath(_)))
+    }
+
+    /// Converts into the format of the deprecated functions, where Err indicates an invalid `$STEAM_DIR`
+    pub(crate) fn into_legacy(self) -> Result<Option<T>, Option<T>> {
+        let err = self.invalid_env();
+        let res = self.result.ok();
+
+        match err {
+            true => Err(res),
+            false => Ok(res)
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl<T> SearchResult<Vec<T>> {
+    /// Same as `into_legacy`, but with an empty Vec when nothing was found
+    pub(crate) fn into_legacy_vec(self) -> Result<Vec<T>, Vec<T>> {
+        let err = self.invalid_env();
+        let res = self.result.unwrap_or_default();
+
+        match err {
+            true => Err(res),
+            false => Ok(res)
+        }
+    }
+}
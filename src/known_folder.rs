@@ -0,0 +1,643 @@
+/// The folders windows knows by a KNOWNFOLDERID, like `FOLDERID_SavedGames`
+///
+/// The doc of each variant notes the default location on windows.
+/// Virtual folders have no path, so they are never resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KnownFolder {
+    /// Virtual folder
+    NetworkFolder,
+    /// Virtual folder
+    ComputerFolder,
+    /// Virtual folder
+    InternetFolder,
+    /// Virtual folder
+    ControlPanelFolder,
+    /// Virtual folder
+    PrintersFolder,
+    /// Virtual folder
+    SyncManagerFolder,
+    /// Virtual folder
+    SyncSetupFolder,
+    /// Virtual folder
+    ConflictFolder,
+    /// Virtual folder
+    SyncResultsFolder,
+    /// Virtual folder
+    RecycleBinFolder,
+    /// Virtual folder
+    ConnectionsFolder,
+    /// `%windir%\Fonts`
+    Fonts,
+    /// `%USERPROFILE%\Desktop`
+    Desktop,
+    /// `%APPDATA%\Microsoft\Windows\Start Menu\Programs\StartUp`
+    Startup,
+    /// `%APPDATA%\Microsoft\Windows\Start Menu\Programs`
+    Programs,
+    /// `%APPDATA%\Microsoft\Windows\Start Menu`
+    StartMenu,
+    /// `%APPDATA%\Microsoft\Windows\Recent`
+    Recent,
+    /// `%APPDATA%\Microsoft\Windows\SendTo`
+    SendTo,
+    /// `%USERPROFILE%\Documents`
+    Documents,
+    /// `%USERPROFILE%\Favorites`
+    Favorites,
+    /// `%APPDATA%\Microsoft\Windows\Network Shortcuts`
+    NetHood,
+    /// `%APPDATA%\Microsoft\Windows\Printer Shortcuts`
+    PrintHood,
+    /// `%APPDATA%\Microsoft\Windows\Templates`
+    Templates,
+    /// `%ALLUSERSPROFILE%\Microsoft\Windows\Start Menu\Programs\StartUp`
+    CommonStartup,
+    /// `%ALLUSERSPROFILE%\Microsoft\Windows\Start Menu\Programs`
+    CommonPrograms,
+    /// `%ALLUSERSPROFILE%\Microsoft\Windows\Start Menu`
+    CommonStartMenu,
+    /// `%PUBLIC%\Desktop`
+    PublicDesktop,
+    /// `%ALLUSERSPROFILE%`
+    ProgramData,
+    /// `%ALLUSERSPROFILE%\Microsoft\Windows\Templates`
+    CommonTemplates,
+    /// `%PUBLIC%\Documents`
+    PublicDocuments,
+    /// `%APPDATA%`
+    RoamingAppData,
+    /// `%LOCALAPPDATA%`
+    LocalAppData,
+    /// `%USERPROFILE%\AppData\LocalLow`
+    LocalAppDataLow,
+    /// `%LOCALAPPDATA%\Microsoft\Windows\Temporary Internet Files`
+    InternetCache,
+    /// `%APPDATA%\Microsoft\Windows\Cookies`
+    Cookies,
+    /// `%LOCALAPPDATA%\Microsoft\Windows\History`
+    History,
+    /// `%windir%\system32`
+    System,
+    /// `%windir%\system32` on 32-bit, `%windir%\SysWOW64` on 64-bit prefixes
+    SystemX86,
+    /// `%windir%`
+    Windows,
+    /// `%USERPROFILE%`
+    Profile,
+    /// `%USERPROFILE%\Pictures`
+    Pictures,
+    /// `%ProgramFiles(x86)%`
+    ProgramFilesX86,
+    /// `%CommonProgramFiles(x86)%`
+    ProgramFilesCommonX86,
+    /// `%ProgramW6432%`
+    ProgramFilesX64,
+    /// `%CommonProgramW6432%`
+    ProgramFilesCommonX64,
+    /// `%ProgramFiles%`
+    ProgramFiles,
+    /// `%CommonProgramFiles%`
+    ProgramFilesCommon,
+    /// `%LOCALAPPDATA%\Programs`
+    UserProgramFiles,
+    /// `%LOCALAPPDATA%\Programs\Common`
+    UserProgramFilesCommon,
+    /// `%APPDATA%\Microsoft\Windows\Start Menu\Programs\Administrative Tools`
+    AdminTools,
+    /// `%ALLUSERSPROFILE%\Microsoft\Windows\Start Menu\Programs\Administrative Tools`
+    CommonAdminTools,
+    /// `%USERPROFILE%\Music`
+    Music,
+    /// `%USERPROFILE%\Videos`
+    Videos,
+    /// `%LOCALAPPDATA%\Microsoft\Windows\Ringtones`
+    Ringtones,
+    /// `%PUBLIC%\Pictures`
+    PublicPictures,
+    /// `%PUBLIC%\Music`
+    PublicMusic,
+    /// `%PUBLIC%\Videos`
+    PublicVideos,
+    /// `%ALLUSERSPROFILE%\Microsoft\Windows\Ringtones`
+    PublicRingtones,
+    /// `%windir%\Resources`
+    ResourceDir,
+    /// `%windir%\resources\<language id>`, not resolved on Linux
+    LocalizedResourcesDir,
+    /// `%ALLUSERSPROFILE%\OEM Links`
+    CommonOEMLinks,
+    /// `%LOCALAPPDATA%\Microsoft\Windows\Burn\Burn`
+    CDBurning,
+    /// `%SystemDrive%\Users`
+    UserProfiles,
+    /// `%USERPROFILE%\Music\Playlists`
+    Playlists,
+    /// `%PUBLIC%\Music\Sample Playlists`
+    SamplePlaylists,
+    /// `%PUBLIC%\Music\Sample Music`
+    SampleMusic,
+    /// `%PUBLIC%\Pictures\Sample Pictures`
+    SamplePictures,
+    /// `%PUBLIC%\Videos\Sample Videos`
+    SampleVideos,
+    /// `%USERPROFILE%\Pictures\Slide Shows`
+    PhotoAlbums,
+    /// `%PUBLIC%`
+    Public,
+    /// Virtual folder
+    ChangeRemovePrograms,
+    /// Virtual folder
+    AppUpdates,
+    /// Virtual folder
+    AddNewPrograms,
+    /// `%USERPROFILE%\Downloads`
+    Downloads,
+    /// `%PUBLIC%\Downloads`
+    PublicDownloads,
+    /// `%USERPROFILE%\Searches`
+    SavedSearches,
+    /// `%APPDATA%\Microsoft\Internet Explorer\Quick Launch`
+    QuickLaunch,
+    /// `%USERPROFILE%\Contacts`
+    Contacts,
+    /// `%LOCALAPPDATA%\Microsoft\Windows Sidebar\Gadgets`
+    SidebarParts,
+    /// `%ProgramFiles%\Windows Sidebar\Gadgets`
+    SidebarDefaultParts,
+    /// `%ALLUSERSPROFILE%\Microsoft\Windows\GameExplorer`
+    PublicGameTasks,
+    /// `%LOCALAPPDATA%\Microsoft\Windows\GameExplorer`
+    GameTasks,
+    /// `%USERPROFILE%\Saved Games`
+    SavedGames,
+    /// Virtual folder
+    Games,
+    /// Virtual folder
+    SearchMapi,
+    /// Virtual folder
+    SearchCsc,
+    /// `%USERPROFILE%\Links`
+    Links,
+    /// Virtual folder
+    UsersFiles,
+    /// Virtual folder
+    UsersLibraries,
+    /// Virtual folder
+    SearchHome,
+    /// `%LOCALAPPDATA%\Microsoft\Windows Photo Gallery\Original Images`
+    OriginalImages,
+    /// `%APPDATA%\Microsoft\Windows\Libraries\Documents.library-ms`
+    DocumentsLibrary,
+    /// `%APPDATA%\Microsoft\Windows\Libraries\Music.library-ms`
+    MusicLibrary,
+    /// `%APPDATA%\Microsoft\Windows\Libraries\Pictures.library-ms`
+    PicturesLibrary,
+    /// `%APPDATA%\Microsoft\Windows\Libraries\Videos.library-ms`
+    VideosLibrary,
+    /// `%PUBLIC%\RecordedTV.library-ms`
+    RecordedTVLibrary,
+    /// Virtual folder
+    HomeGroup,
+    /// Virtual folder
+    HomeGroupCurrentUser,
+    /// `%ALLUSERSPROFILE%\Microsoft\Windows\DeviceMetadataStore`
+    DeviceMetadataStore,
+    /// `%APPDATA%\Microsoft\Windows\Libraries`
+    Libraries,
+    /// `%PUBLIC%\Libraries`
+    PublicLibraries,
+    /// `%APPDATA%\Microsoft\Internet Explorer\Quick Launch\User Pinned`
+    UserPinned,
+    /// `%APPDATA%\Microsoft\Internet Explorer\Quick Launch\User Pinned\ImplicitAppShortcuts`
+    ImplicitAppShortcuts,
+    /// `%APPDATA%\Microsoft\Windows\AccountPictures`
+    AccountPictures,
+    /// `%PUBLIC%\AccountPictures`
+    PublicUserTiles,
+    /// Virtual folder
+    AppsFolder,
+    /// Virtual folder
+    StartMenuAllPrograms,
+    /// Virtual folder
+    CommonStartMenuPlaces,
+    /// `%LOCALAPPDATA%\Microsoft\Windows\Application Shortcuts`
+    ApplicationShortcuts,
+    /// `%LOCALAPPDATA%\Microsoft\Windows\RoamingTiles`
+    RoamingTiles,
+    /// `%LOCALAPPDATA%\Microsoft\Windows\RoamedTileImages`
+    RoamedTileImages,
+    /// `%USERPROFILE%\Pictures\Screenshots`
+    Screenshots,
+    /// `%USERPROFILE%\Pictures\Camera Roll`
+    CameraRoll,
+    /// `%USERPROFILE%\OneDrive`
+    OneDrive,
+    /// `%USERPROFILE%\OneDrive\Documents`
+    OneDriveDocuments,
+    /// `%USERPROFILE%\OneDrive\Pictures`
+    OneDrivePictures,
+    /// `%USERPROFILE%\OneDrive\Music`
+    OneDriveMusic,
+    /// `%USERPROFILE%\OneDrive\Pictures\Camera Roll`
+    OneDriveCameraRoll,
+    /// `%LOCALAPPDATA%\Microsoft\Windows\ConnectedSearch\History`
+    SearchHistory,
+    /// `%LOCALAPPDATA%\Microsoft\Windows\ConnectedSearch\Templates`
+    SearchTemplates,
+    /// `%APPDATA%\Microsoft\Windows\Libraries\CameraRoll.library-ms`
+    CameraRollLibrary,
+    /// `%USERPROFILE%\Pictures\Saved Pictures`
+    SavedPictures,
+    /// `%APPDATA%\Microsoft\Windows\Libraries\SavedPictures.library-ms`
+    SavedPicturesLibrary,
+    /// `%ALLUSERSPROFILE%\Microsoft\Windows\RetailDemo`
+    RetailDemo,
+    /// Virtual folder
+    Device,
+    /// `%LOCALAPPDATA%\DevelopmentFiles`
+    DevelopmentFiles,
+    /// `%USERPROFILE%\3D Objects`
+    Objects3D,
+    /// `%USERPROFILE%\Videos\Captures`
+    AppCaptures,
+    /// `%USERPROFILE%\Documents`
+    LocalDocuments,
+    /// `%USERPROFILE%\Pictures`
+    LocalPictures,
+    /// `%USERPROFILE%\Videos`
+    LocalVideos,
+    /// `%USERPROFILE%\Music`
+    LocalMusic,
+    /// `%USERPROFILE%\Downloads`
+    LocalDownloads,
+    /// Not resolved on Linux
+    RecordedCalls,
+    /// Not resolved on Linux
+    AllAppMods,
+    /// Not resolved on Linux
+    CurrentAppMods,
+    /// `%LOCALAPPDATA%\Desktop`
+    AppDataDesktop,
+    /// `%LOCALAPPDATA%\Documents`
+    AppDataDocuments,
+    /// `%LOCALAPPDATA%\Favorites`
+    AppDataFavorites,
+    /// `%LOCALAPPDATA%\ProgramData`
+    AppDataProgramData,
+    /// Not resolved on Linux
+    LocalStorage
+}
+
+impl KnownFolder {
+    /// All known folders
+    pub const ALL: [KnownFolder; 141] = [
+        KnownFolder::NetworkFolder,
+        KnownFolder::ComputerFolder,
+        KnownFolder::InternetFolder,
+        KnownFolder::ControlPanelFolder,
+        KnownFolder::PrintersFolder,
+        KnownFolder::SyncManagerFolder,
+        KnownFolder::SyncSetupFolder,
+        KnownFolder::ConflictFolder,
+        KnownFolder::SyncResultsFolder,
+        KnownFolder::RecycleBinFolder,
+        KnownFolder::ConnectionsFolder,
+        KnownFolder::Fonts,
+        KnownFolder::Desktop,
+        KnownFolder::Startup,
+        KnownFolder::Programs,
+        KnownFolder::StartMenu,
+        KnownFolder::Recent,
+        KnownFolder::SendTo,
+        KnownFolder::Documents,
+        KnownFolder::Favorites,
+        KnownFolder::NetHood,
+        KnownFolder::PrintHood,
+        KnownFolder::Templates,
+        KnownFolder::CommonStartup,
+        KnownFolder::CommonPrograms,
+        KnownFolder::CommonStartMenu,
+        KnownFolder::PublicDesktop,
+        KnownFolder::ProgramData,
+        KnownFolder::CommonTemplates,
+        KnownFolder::PublicDocuments,
+        KnownFolder::RoamingAppData,
+        KnownFolder::LocalAppData,
+        KnownFolder::LocalAppDataLow,
+        KnownFolder::InternetCache,
+        KnownFolder::Cookies,
+        KnownFolder::History,
+        KnownFolder::System,
+        KnownFolder::SystemX86,
+        KnownFolder::Windows,
+        KnownFolder::Profile,
+        KnownFolder::Pictures,
+        KnownFolder::ProgramFilesX86,
+        KnownFolder::ProgramFilesCommonX86,
+        KnownFolder::ProgramFilesX64,
+        KnownFolder::ProgramFilesCommonX64,
+        KnownFolder::ProgramFiles,
+        KnownFolder::ProgramFilesCommon,
+        KnownFolder::UserProgramFiles,
+        KnownFolder::UserProgramFilesCommon,
+        KnownFolder::AdminTools,
+        KnownFolder::CommonAdminTools,
+        KnownFolder::Music,
+        KnownFolder::Videos,
+        KnownFolder::Ringtones,
+        KnownFolder::PublicPictures,
+        KnownFolder::PublicMusic,
+        KnownFolder::PublicVideos,
+        KnownFolder::PublicRingtones,
+        KnownFolder::ResourceDir,
+        KnownFolder::LocalizedResourcesDir,
+        KnownFolder::CommonOEMLinks,
+        KnownFolder::CDBurning,
+        KnownFolder::UserProfiles,
+        KnownFolder::Playlists,
+        KnownFolder::SamplePlaylists,
+        KnownFolder::SampleMusic,
+        KnownFolder::SamplePictures,
+        KnownFolder::SampleVideos,
+        KnownFolder::PhotoAlbums,
+        KnownFolder::Public,
+        KnownFolder::ChangeRemovePrograms,
+        KnownFolder::AppUpdates,
+        KnownFolder::AddNewPrograms,
+        KnownFolder::Downloads,
+        KnownFolder::PublicDownloads,
+        KnownFolder::SavedSearches,
+        KnownFolder::QuickLaunch,
+        KnownFolder::Contacts,
+        KnownFolder::SidebarParts,
+        KnownFolder::SidebarDefaultParts,
+        KnownFolder::PublicGameTasks,
+        KnownFolder::GameTasks,
+        KnownFolder::SavedGames,
+        KnownFolder::Games,
+        KnownFolder::SearchMapi,
+        KnownFolder::SearchCsc,
+        KnownFolder::Links,
+        KnownFolder::UsersFiles,
+        KnownFolder::UsersLibraries,
+        KnownFolder::SearchHome,
+        KnownFolder::OriginalImages,
+        KnownFolder::DocumentsLibrary,
+        KnownFolder::MusicLibrary,
+        KnownFolder::PicturesLibrary,
+        KnownFolder::VideosLibrary,
+        KnownFolder::RecordedTVLibrary,
+        KnownFolder::HomeGroup,
+        KnownFolder::HomeGroupCurrentUser,
+        KnownFolder::DeviceMetadataStore,
+        KnownFolder::Libraries,
+        KnownFolder::PublicLibraries,
+        KnownFolder::UserPinned,
+        KnownFolder::ImplicitAppShortcuts,
+        KnownFolder::AccountPictures,
+        KnownFolder::PublicUserTiles,
+        KnownFolder::AppsFolder,
+        KnownFolder::StartMenuAllPrograms,
+        KnownFolder::CommonStartMenuPlaces,
+        KnownFolder::ApplicationShortcuts,
+        KnownFolder::RoamingTiles,
+        KnownFolder::RoamedTileImages,
+        KnownFolder::Screenshots,
+        KnownFolder::CameraRoll,
+        KnownFolder::OneDrive,
+        KnownFolder::OneDriveDocuments,
+        KnownFolder::OneDrivePictures,
+        KnownFolder::OneDriveMusic,
+        KnownFolder::OneDriveCameraRoll,
+        KnownFolder::SearchHistory,
+        KnownFolder::SearchTemplates,
+        KnownFolder::CameraRollLibrary,
+        KnownFolder::SavedPictures,
+        KnownFolder::SavedPicturesLibrary,
+        KnownFolder::RetailDemo,
+        KnownFolder::Device,
+        KnownFolder::DevelopmentFiles,
+        KnownFolder::Objects3D,
+        KnownFolder::AppCaptures,
+        KnownFolder::LocalDocuments,
+        KnownFolder::LocalPictures,
+        KnownFolder::LocalVideos,
+        KnownFolder::LocalMusic,
+        KnownFolder::LocalDownloads,
+        KnownFolder::RecordedCalls,
+        KnownFolder::AllAppMods,
+        KnownFolder::CurrentAppMods,
+        KnownFolder::AppDataDesktop,
+        KnownFolder::AppDataDocuments,
+        KnownFolder::AppDataFavorites,
+        KnownFolder::AppDataProgramData,
+        KnownFolder::LocalStorage
+    ];
+
+    /// The KNOWNFOLDERID, formated like `{4C5C32FF-BB9D-43B0-B5B4-2D72E54EAAA4}`
+    pub fn guid(&self) -> &'static str {
+        self.definition().0
+    }
+
+    /// Finds the folder by its KNOWNFOLDERID (ignoring case, braces are optional)
+    pub fn from_guid(guid: &str) -> Option<KnownFolder> {
+        let guid = guid.trim_start_matches('{').trim_end_matches('}');
+        Self::ALL.iter().copied().find(|folder| folder.guid()[1..37].eq_ignore_ascii_case(guid))
+    }
+
+    /// The default location on windows, with environment variables
+    pub fn default_path(&self) -> Option<&'static str> {
+        self.definition().1
+    }
+
+    /// The name of the value in `HKCU\Software\Microsoft\Windows\CurrentVersion\Explorer\Shell Folders`.
+    ///
+    /// Newer folders don't have a name, for them the guid is used
+    pub fn user_shell_folder(&self) -> Option<&'static str> {
+        self.definition().2
+    }
+
+    /// The name of the value in `HKLM\Software\Microsoft\Windows\CurrentVersion\Explorer\Shell Folders`
+    pub fn common_shell_folder(&self) -> Option<&'static str> {
+        self.definition().3
+    }
+
+    /// If this is a virtual folder (like the control panel), which has no path
+    pub fn is_virtual(&self) -> bool {
+        matches!(self,
+            KnownFolder::NetworkFolder |
+            KnownFolder::ComputerFolder |
+            KnownFolder::InternetFolder |
+            KnownFolder::ControlPanelFolder |
+            KnownFolder::PrintersFolder |
+            KnownFolder::SyncManagerFolder |
+            KnownFolder::SyncSetupFolder |
+            KnownFolder::ConflictFolder |
+            KnownFolder::SyncResultsFolder |
+            KnownFolder::RecycleBinFolder |
+            KnownFolder::ConnectionsFolder |
+            KnownFolder::ChangeRemovePrograms |
+            KnownFolder::AppUpdates |
+            KnownFolder::AddNewPrograms |
+            KnownFolder::Games |
+            KnownFolder::SearchMapi |
+            KnownFolder::SearchCsc |
+            KnownFolder::UsersFiles |
+            KnownFolder::UsersLibraries |
+            KnownFolder::SearchHome |
+            KnownFolder::HomeGroup |
+            KnownFolder::HomeGroupCurrentUser |
+            KnownFolder::AppsFolder |
+            KnownFolder::StartMenuAllPrograms |
+            KnownFolder::CommonStartMenuPlaces |
+            KnownFolder::Device
+        )
+    }
+
+    /// (guid, default path, user shell folder, common shell folder)
+    fn definition(&self) -> (&'static str, Option<&'static str>, Option<&'static str>, Option<&'static str>) {
+        match self {
+            KnownFolder::NetworkFolder => ("{D20BEEC4-5CA8-4905-AE3B-BF251EA09B53}", None, None, None),
+            KnownFolder::ComputerFolder => ("{0AC0837C-BBF8-452A-850D-79D08E667CA7}", None, None, None),
+            KnownFolder::InternetFolder => ("{4D9F7874-4E0C-4904-967B-40B0D20C3E4B}", None, None, None),
+            KnownFolder::ControlPanelFolder => ("{82A74AEB-AEB4-465C-A014-D097EE346D63}", None, None, None),
+            KnownFolder::PrintersFolder => ("{76FC4E2D-D6AD-4519-A663-37BD56068185}", None, None, None),
+            KnownFolder::SyncManagerFolder => ("{43668BF8-C14E-49B2-97C9-747784D784B7}", None, None, None),
+            KnownFolder::SyncSetupFolder => ("{0F214138-B1D3-4A90-BBA9-27CBC0C5389A}", None, None, None),
+            KnownFolder::ConflictFolder => ("{4BFEFB45-347D-4006-A5BE-AC0CB0567192}", None, None, None),
+            KnownFolder::SyncResultsFolder => ("{289A9A43-BE44-4057-A41B-587A76D7E7F9}", None, None, None),
+            KnownFolder::RecycleBinFolder => ("{B7534046-3ECB-4C18-BE4E-64CD4CB7D6AC}", None, None, None),
+            KnownFolder::ConnectionsFolder => ("{6F0CD92B-2E97-45D1-88FF-B0D186B8DEDD}", None, None, None),
+            KnownFolder::Fonts => ("{FD228CB7-AE11-4AE3-864C-16F3910AB8FE}", Some("%windir%\\Fonts"), Some("Fonts"), None),
+            KnownFolder::Desktop => ("{B4BFCC3A-DB2C-424C-B029-7FE99A87C641}", Some("%USERPROFILE%\\Desktop"), Some("Desktop"), None),
+            KnownFolder::Startup => ("{B97D20BB-F46A-4C97-BA10-5E3608430854}", Some("%APPDATA%\\Microsoft\\Windows\\Start Menu\\Programs\\StartUp"), Some("Startup"), None),
+            KnownFolder::Programs => ("{A77F5D77-2E2B-44C3-A6A2-ABA601054A51}", Some("%APPDATA%\\Microsoft\\Windows\\Start Menu\\Programs"), Some("Programs"), None),
+            KnownFolder::StartMenu => ("{625B53C3-AB48-4EC1-BA1F-A1EF4146FC19}", Some("%APPDATA%\\Microsoft\\Windows\\Start Menu"), Some("Start Menu"), None),
+            KnownFolder::Recent => ("{AE50C081-EBD2-438A-8655-8A092E34987A}", Some("%APPDATA%\\Microsoft\\Windows\\Recent"), Some("Recent"), None),
+            KnownFolder::SendTo => ("{8983036C-27C0-404B-8F08-102D10DCFD74}", Some("%APPDATA%\\Microsoft\\Windows\\SendTo"), Some("SendTo"), None),
+            KnownFolder::Documents => ("{FDD39AD0-238F-46AF-ADB4-6C85480369C7}", Some("%USERPROFILE%\\Documents"), Some("Personal"), None),
+            KnownFolder::Favorites => ("{1777F761-68AD-4D8A-87BD-30B759FA33DD}", Some("%USERPROFILE%\\Favorites"), Some("Favorites"), None),
+            KnownFolder::NetHood => ("{C5ABBF53-E17F-4121-8900-86626FC2C973}", Some("%APPDATA%\\Microsoft\\Windows\\Network Shortcuts"), Some("NetHood"), None),
+            KnownFolder::PrintHood => ("{9274BD8D-CFD1-41C3-B35E-B13F55A758F4}", Some("%APPDATA%\\Microsoft\\Windows\\Printer Shortcuts"), Some("PrintHood"), None),
+            KnownFolder::Templates => ("{A63293E8-664E-48DB-A079-DF759E0509F7}", Some("%APPDATA%\\Microsoft\\Windows\\Templates"), Some("Templates"), None),
+            KnownFolder::CommonStartup => ("{82A5EA35-D9CD-47C5-9629-E15D2F714E6E}", Some("%ALLUSERSPROFILE%\\Microsoft\\Windows\\Start Menu\\Programs\\StartUp"), None, Some("Common Startup")),
+            KnownFolder::CommonPrograms => ("{0139D44E-6AFE-49F2-8690-3DAFCAE6FFB8}", Some("%ALLUSERSPROFILE%\\Microsoft\\Windows\\Start Menu\\Programs"), None, Some("Common Programs")),
+            KnownFolder::CommonStartMenu => ("{A4115719-D62E-491D-AA7C-E74B8BE3B067}", Some("%ALLUSERSPROFILE%\\Microsoft\\Windows\\Start Menu"), None, Some("Common Start Menu")),
+            KnownFolder::PublicDesktop => ("{C4AA340D-F20F-4863-AFEF-F87EF2E6BA25}", Some("%PUBLIC%\\Desktop"), None, Some("Common Desktop")),
+            KnownFolder::ProgramData => ("{62AB5D82-FDC1-4DC3-A9DD-070D1D495D97}", Some("%ALLUSERSPROFILE%"), None, Some("Common AppData")),
+            KnownFolder::CommonTemplates => ("{B94237E7-57AC-4347-9151-B08C6C32D1F7}", Some("%ALLUSERSPROFILE%\\Microsoft\\Windows\\Templates"), None, Some("Common Templates")),
+            KnownFolder::PublicDocuments => ("{ED4824AF-DCE4-45A8-81E2-FC7965083634}", Some("%PUBLIC%\\Documents"), None, Some("Common Documents")),
+            KnownFolder::RoamingAppData => ("{3EB685DB-65F9-4CF6-A03A-E3EF65729F3D}", Some("%APPDATA%"), Some("AppData"), None),
+            KnownFolder::LocalAppData => ("{F1B32785-6FBA-4FCF-9D55-7B8E7F157091}", Some("%LOCALAPPDATA%"), Some("Local AppData"), None),
+            KnownFolder::LocalAppDataLow => ("{A520A1A4-1780-4FF6-BD18-167343C5AF16}", Some("%USERPROFILE%\\AppData\\LocalLow"), None, None),
+            KnownFolder::InternetCache => ("{352481E8-33BE-4251-BA85-6007CAEDCF9D}", Some("%LOCALAPPDATA%\\Microsoft\\Windows\\Temporary Internet Files"), Some("Cache"), None),
+            KnownFolder::Cookies => ("{2B0F765D-C0E9-4171-908E-08A611B84FF6}", Some("%APPDATA%\\Microsoft\\Windows\\Cookies"), Some("Cookies"), None),
+            KnownFolder::History => ("{D9DC8A3B-B784-432E-A781-5A1130A75963}", Some("%LOCALAPPDATA%\\Microsoft\\Windows\\History"), Some("History"), None),
+            KnownFolder::System => ("{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}", Some("%windir%\\system32"), None, None),
+            KnownFolder::SystemX86 => ("{D65231B0-B2F1-4857-A4CE-A8E7C6EA7D27}", None, None, None),
+            KnownFolder::Windows => ("{F38BF404-1D43-42F2-9305-67DE0B28FC23}", Some("%windir%"), None, None),
+            KnownFolder::Profile => ("{5E6C858F-0E22-4760-9AFE-EA3317B67173}", Some("%USERPROFILE%"), None, None),
+            KnownFolder::Pictures => ("{33E28130-4E1E-4676-835A-98395C3BC3BB}", Some("%USERPROFILE%\\Pictures"), Some("My Pictures"), None),
+            KnownFolder::ProgramFilesX86 => ("{7C5A40EF-A0FB-4BFC-874A-C0F2E0B9FA8E}", Some("%ProgramFiles(x86)%"), None, None),
+            KnownFolder::ProgramFilesCommonX86 => ("{DE974D24-D9C6-4D3E-BF91-F4455120B917}", Some("%CommonProgramFiles(x86)%"), None, None),
+            KnownFolder::ProgramFilesX64 => ("{6D809377-6AF0-444B-8957-A3773F02200E}", Some("%ProgramW6432%"), None, None),
+            KnownFolder::ProgramFilesCommonX64 => ("{6365D5A7-0F0D-45E5-87F6-0DA56B6A4F7D}", Some("%CommonProgramW6432%"), None, None),
+            KnownFolder::ProgramFiles => ("{905E63B6-C1BF-494E-B29C-65B732D3D21A}", Some("%ProgramFiles%"), None, None),
+            KnownFolder::ProgramFilesCommon => ("{F7F1ED05-9F6D-47A2-AAAE-29D317C6F066}", Some("%CommonProgramFiles%"), None, None),
+            KnownFolder::UserProgramFiles => ("{5CD7AEE2-2219-4A67-B85D-6C9CE15660CB}", Some("%LOCALAPPDATA%\\Programs"), None, None),
+            KnownFolder::UserProgramFilesCommon => ("{BCBD3057-CA5C-4622-B42D-BC56DB0AE516}", Some("%LOCALAPPDATA%\\Programs\\Common"), None, None),
+            KnownFolder::AdminTools => ("{724EF170-A42D-4FEF-9F26-B60E846FBA4F}", Some("%APPDATA%\\Microsoft\\Windows\\Start Menu\\Programs\\Administrative Tools"), Some("Administrative Tools"), None),
+            KnownFolder::CommonAdminTools => ("{D0384E7D-BAC3-4797-8F14-CBA229B392B5}", Some("%ALLUSERSPROFILE%\\Microsoft\\Windows\\Start Menu\\Programs\\Administrative Tools"), None, Some("Common Administrative Tools")),
+            KnownFolder::Music => ("{4BD8D571-6D19-48D3-BE97-422220080E43}", Some("%USERPROFILE%\\Music"), Some("My Music"), None),
+            KnownFolder::Videos => ("{18989B1D-99B5-455B-841C-AB7C74E4DDFC}", Some("%USERPROFILE%\\Videos"), Some("My Videos"), None),
+            KnownFolder::Ringtones => ("{C870044B-F49E-4126-A9C3-B52A1FF411E8}", Some("%LOCALAPPDATA%\\Microsoft\\Windows\\Ringtones"), None, None),
+            KnownFolder::PublicPictures => ("{B6EBFB86-6907-413C-9AF7-4FC2ABF07CC5}", Some("%PUBLIC%\\Pictures"), None, Some("CommonPictures")),
+            KnownFolder::PublicMusic => ("{3214FAB5-9757-4298-BB61-92A9DEAA44FF}", Some("%PUBLIC%\\Music"), None, Some("CommonMusic")),
+            KnownFolder::PublicVideos => ("{2400183A-6185-49FB-A2D8-4A392A602BA3}", Some("%PUBLIC%\\Videos"), None, Some("CommonVideo")),
+            KnownFolder::PublicRingtones => ("{E555AB60-153B-4D17-9F04-A5FE99FC15EC}", Some("%ALLUSERSPROFILE%\\Microsoft\\Windows\\Ringtones"), None, None),
+            KnownFolder::ResourceDir => ("{8AD10C31-2ADB-4296-A8F7-E4701232C972}", Some("%windir%\\Resources"), None, None),
+            KnownFolder::LocalizedResourcesDir => ("{2A00375E-224C-49DE-B8D1-440DF7EF3DDC}", None, None, None),
+            KnownFolder::CommonOEMLinks => ("{C1BAE2D0-10DF-4334-BEDD-7AA20B227A9D}", Some("%ALLUSERSPROFILE%\\OEM Links"), None, None),
+            KnownFolder::CDBurning => ("{9E52AB10-F80D-49DF-ACB8-4330F5687855}", Some("%LOCALAPPDATA%\\Microsoft\\Windows\\Burn\\Burn"), Some("CD Burning"), None),
+            KnownFolder::UserProfiles => ("{0762D272-C50A-4BB0-A382-697DCD729B80}", Some("%SystemDrive%\\Users"), None, None),
+            KnownFolder::Playlists => ("{DE92C1C7-837F-4F69-A3BB-86E631204A23}", Some("%USERPROFILE%\\Music\\Playlists"), None, None),
+            KnownFolder::SamplePlaylists => ("{15CA69B3-30EE-49C1-ACE1-6B5EC372AFB5}", Some("%PUBLIC%\\Music\\Sample Playlists"), None, None),
+            KnownFolder::SampleMusic => ("{B250C668-F57D-4EE1-A63C-290EE7D1AA1F}", Some("%PUBLIC%\\Music\\Sample Music"), None, None),
+            KnownFolder::SamplePictures => ("{C4900540-2379-4C75-844B-64E6FAF8716B}", Some("%PUBLIC%\\Pictures\\Sample Pictures"), None, None),
+            KnownFolder::SampleVideos => ("{859EAD94-2E85-48AD-A71A-0969CB56A6CD}", Some("%PUBLIC%\\Videos\\Sample Videos"), None, None),
+            KnownFolder::PhotoAlbums => ("{69D2CF90-FC33-4FB7-9A0C-EBB0F0FCB43C}", Some("%USERPROFILE%\\Pictures\\Slide Shows"), None, None),
+            KnownFolder::Public => ("{DFDF76A2-C82A-4D63-906A-5644AC457385}", Some("%PUBLIC%"), None, None),
+            KnownFolder::ChangeRemovePrograms => ("{DF7266AC-9274-4867-8D55-3BD661DE872D}", None, None, None),
+            KnownFolder::AppUpdates => ("{A305CE99-F527-492B-8B1A-7E76FA98D6E4}", None, None, None),
+            KnownFolder::AddNewPrograms => ("{DE61D971-5EBC-4F02-A3A9-6C82895E5C04}", None, None, None),
+            KnownFolder::Downloads => ("{374DE290-123F-4565-9164-39C4925E467B}", Some("%USERPROFILE%\\Downloads"), None, None),
+            KnownFolder::PublicDownloads => ("{3D644C9B-1FB8-4F30-9B45-F670235F79C0}", Some("%PUBLIC%\\Downloads"), None, None),
+            KnownFolder::SavedSearches => ("{7D1D3A04-DEBB-4115-95CF-2F29DA2920DA}", Some("%USERPROFILE%\\Searches"), None, None),
+            KnownFolder::QuickLaunch => ("{52A4F021-7B75-48A9-9F6B-4B87A210BC8F}", Some("%APPDATA%\\Microsoft\\Internet Explorer\\Quick Launch"), None, None),
+            KnownFolder::Contacts => ("{56784854-C6CB-462B-8169-88E350ACB882}", Some("%USERPROFILE%\\Contacts"), None, None),
+            KnownFolder::SidebarParts => ("{A75D362E-50FC-4FB7-AC2C-A8BEAA314493}", Some("%LOCALAPPDATA%\\Microsoft\\Windows Sidebar\\Gadgets"), None, None),
+            KnownFolder::SidebarDefaultParts => ("{7B396E54-9EC5-4300-BE0A-2482EBAE1A26}", Some("%ProgramFiles%\\Windows Sidebar\\Gadgets"), None, None),
+            KnownFolder::PublicGameTasks => ("{DEBF2536-E1A8-4C59-B6A2-414586476AEA}", Some("%ALLUSERSPROFILE%\\Microsoft\\Windows\\GameExplorer"), None, None),
+            KnownFolder::GameTasks => ("{054FAE61-4DD8-4787-80B6-090220C4B700}", Some("%LOCALAPPDATA%\\Microsoft\\Windows\\GameExplorer"), None, None),
+            KnownFolder::SavedGames => ("{4C5C32FF-BB9D-43B0-B5B4-2D72E54EAAA4}", Some("%USERPROFILE%\\Saved Games"), None, None),
+            KnownFolder::Games => ("{CAC52C1A-B53D-4EDC-92D7-6B2E8AC19434}", None, None, None),
+            KnownFolder::SearchMapi => ("{98EC0E18-2098-4D44-8644-66979315A281}", None, None, None),
+            KnownFolder::SearchCsc => ("{EE32E446-31CA-4ABA-814F-A5EBD2FD6D5E}", None, None, None),
+            KnownFolder::Links => ("{BFB9D5E0-C6A9-404C-B2B2-AE6DB6AF4968}", Some("%USERPROFILE%\\Links"), None, None),
+            KnownFolder::UsersFiles => ("{F3CE0F7C-4901-4ACC-8648-D5D44B04EF8F}", None, None, None),
+            KnownFolder::UsersLibraries => ("{A302545D-DEFF-464B-ABE8-61C8648D939B}", None, None, None),
+            KnownFolder::SearchHome => ("{190337D1-B8CA-4121-A639-6D472D16972A}", None, None, None),
+            KnownFolder::OriginalImages => ("{2C36C0AA-5812-4B87-BFD0-4CD0DFB19B39}", Some("%LOCALAPPDATA%\\Microsoft\\Windows Photo Gallery\\Original Images"), None, None),
+            KnownFolder::DocumentsLibrary => ("{7B0DB17D-9CD2-4A93-9733-46CC89022E7C}", Some("%APPDATA%\\Microsoft\\Windows\\Libraries\\Documents.library-ms"), None, None),
+            KnownFolder::MusicLibrary => ("{2112AB0A-C86A-4FFE-A368-0DE96E47012E}", Some("%APPDATA%\\Microsoft\\Windows\\Libraries\\Music.library-ms"), None, None),
+            KnownFolder::PicturesLibrary => ("{A990AE9F-A03B-4E80-94BC-9912D7504104}", Some("%APPDATA%\\Microsoft\\Windows\\Libraries\\Pictures.library-ms"), None, None),
+            KnownFolder::VideosLibrary => ("{491E922F-5643-4AF4-A7EB-4E7A138D8174}", Some("%APPDATA%\\Microsoft\\Windows\\Libraries\\Videos.library-ms"), None, None),
+            KnownFolder::RecordedTVLibrary => ("{1A6FDBA2-F42D-4358-A798-B74D745926C5}", Some("%PUBLIC%\\RecordedTV.library-ms"), None, None),
+            KnownFolder::HomeGroup => ("{52528A6B-B9E3-4ADD-B60D-588C2DBA842D}", None, None, None),
+            KnownFolder::HomeGroupCurrentUser => ("{9B74B6A3-0DFD-4F11-9E78-5F7800F2E772}", None, None, None),
+            KnownFolder::DeviceMetadataStore => ("{5CE4A5E9-E4EB-479D-B89F-130C02886155}", Some("%ALLUSERSPROFILE%\\Microsoft\\Windows\\DeviceMetadataStore"), None, None),
+            KnownFolder::Libraries => ("{1B3EA5DC-B587-4786-B4EF-BD1DC332AEAE}", Some("%APPDATA%\\Microsoft\\Windows\\Libraries"), None, None),
+            KnownFolder::PublicLibraries => ("{48DAF80B-E6CF-4F4E-B800-0E69D84EE384}", Some("%PUBLIC%\\Libraries"), None, None),
+            KnownFolder::UserPinned => ("{9E3995AB-1F9C-4F13-B827-48B24B6C7174}", Some("%APPDATA%\\Microsoft\\Internet Explorer\\Quick Launch\\User Pinned"), None, None),
+            KnownFolder::ImplicitAppShortcuts => ("{BCB5256F-79F6-4CEE-B725-DC34E402FD46}", Some("%APPDATA%\\Microsoft\\Internet Explorer\\Quick Launch\\User Pinned\\ImplicitAppShortcuts"), None, None),
+            KnownFolder::AccountPictures => ("{008CA0B1-55B4-4C56-B8A8-4DE4B299D3BE}", Some("%APPDATA%\\Microsoft\\Windows\\AccountPictures"), None, None),
+            KnownFolder::PublicUserTiles => ("{0482AF6C-08F1-4C34-8C90-E17EC98B1E17}", Some("%PUBLIC%\\AccountPictures"), None, None),
+            KnownFolder::AppsFolder => ("{1E87508D-89C2-42F0-8A7E-645A0F50CA58}", None, None, None),
+            KnownFolder::StartMenuAllPrograms => ("{F26305EF-6948-40B9-B255-81453D09C785}", None, None, None),
+            KnownFolder::CommonStartMenuPlaces => ("{A440879F-87A0-4F7D-B700-0207B966194A}", None, None, None),
+            KnownFolder::ApplicationShortcuts => ("{A3918781-E5F2-4890-B3D9-A7E54332328C}", Some("%LOCALAPPDATA%\\Microsoft\\Windows\\Application Shortcuts"), None, None),
+            KnownFolder::RoamingTiles => ("{00BCFC5A-ED94-4E48-96A1-3F6217F21990}", Some("%LOCALAPPDATA%\\Microsoft\\Windows\\RoamingTiles"), None, None),
+            KnownFolder::RoamedTileImages => ("{AAA8D5A5-F1D6-4259-BAA8-78E7EF60835E}", Some("%LOCALAPPDATA%\\Microsoft\\Windows\\RoamedTileImages"), None, None),
+            KnownFolder::Screenshots => ("{B7BEDE81-DF94-4682-A7D8-57A52620B86F}", Some("%USERPROFILE%\\Pictures\\Screenshots"), None, None),
+            KnownFolder::CameraRoll => ("{AB5FB87B-7CE2-4F83-915D-550846C9537B}", Some("%USERPROFILE%\\Pictures\\Camera Roll"), None, None),
+            KnownFolder::OneDrive => ("{A52BBA46-E9E1-435F-B3D9-28DAA648C0F6}", Some("%USERPROFILE%\\OneDrive"), None, None),
+            KnownFolder::OneDriveDocuments => ("{24D89E24-2F19-4534-9DDE-6A6671FBB8FE}", Some("%USERPROFILE%\\OneDrive\\Documents"), None, None),
+            KnownFolder::OneDrivePictures => ("{339719B5-8C47-4894-94C2-D8F77ADD44A6}", Some("%USERPROFILE%\\OneDrive\\Pictures"), None, None),
+            KnownFolder::OneDriveMusic => ("{C3F2459E-80D6-45DC-BFEF-1F769F2BE730}", Some("%USERPROFILE%\\OneDrive\\Music"), None, None),
+            KnownFolder::OneDriveCameraRoll => ("{767E6811-49CB-4273-87C2-20F355E1085B}", Some("%USERPROFILE%\\OneDrive\\Pictures\\Camera Roll"), None, None),
+            KnownFolder::SearchHistory => ("{0D4C3DB6-03A3-462F-A0E6-08924C41B5D4}", Some("%LOCALAPPDATA%\\Microsoft\\Windows\\ConnectedSearch\\History"), None, None),
+            KnownFolder::SearchTemplates => ("{7E636BFE-DFA9-4D5E-B456-D7B39851D8A9}", Some("%LOCALAPPDATA%\\Microsoft\\Windows\\ConnectedSearch\\Templates"), None, None),
+            KnownFolder::CameraRollLibrary => ("{2B20DF75-1EDA-4039-8097-38798227D5B7}", Some("%APPDATA%\\Microsoft\\Windows\\Libraries\\CameraRoll.library-ms"), None, None),
+            KnownFolder::SavedPictures => ("{3B193882-D3AD-4EAB-965A-69829D1FB59F}", Some("%USERPROFILE%\\Pictures\\Saved Pictures"), None, None),
+            KnownFolder::SavedPicturesLibrary => ("{E25B5812-BE88-4BD9-94B0-29233477B6C3}", Some("%APPDATA%\\Microsoft\\Windows\\Libraries\\SavedPictures.library-ms"), None, None),
+            KnownFolder::RetailDemo => ("{12D4C69E-24AD-4923-BE19-31321C43A767}", Some("%ALLUSERSPROFILE%\\Microsoft\\Windows\\RetailDemo"), None, None),
+            KnownFolder::Device => ("{1C2AC1DC-4358-4B6C-9733-AF21156576F0}", None, None, None),
+            KnownFolder::DevelopmentFiles => ("{DBE8E08E-3053-4BBC-B183-2A7B2B191E59}", Some("%LOCALAPPDATA%\\DevelopmentFiles"), None, None),
+            KnownFolder::Objects3D => ("{31C0DD25-9439-4F12-BF41-7FF4EDA38722}", Some("%USERPROFILE%\\3D Objects"), None, None),
+            KnownFolder::AppCaptures => ("{EDC0FE71-98D8-4F4A-B920-C8DC133CB165}", Some("%USERPROFILE%\\Videos\\Captures"), None, None),
+            KnownFolder::LocalDocuments => ("{F42EE2D3-909F-4907-8871-4C22FC0BF756}", Some("%USERPROFILE%\\Documents"), None, None),
+            KnownFolder::LocalPictures => ("{0DDD015D-B06C-45D5-8C4C-F59713854639}", Some("%USERPROFILE%\\Pictures"), None, None),
+            KnownFolder::LocalVideos => ("{35286A68-3C57-41A1-BBB1-0EAE73D76C95}", Some("%USERPROFILE%\\Videos"), None, None),
+            KnownFolder::LocalMusic => ("{A0C69A99-21C8-4671-8703-7934162FCF1D}", Some("%USERPROFILE%\\Music"), None, None),
+            KnownFolder::LocalDownloads => ("{7D83EE9B-2244-4E70-B1F5-5393042AF1E4}", Some("%USERPROFILE%\\Downloads"), None, None),
+            KnownFolder::RecordedCalls => ("{2F8B40C2-83ED-48EE-B383-A1F157EC6F9A}", None, None, None),
+            KnownFolder::AllAppMods => ("{7AD67899-66AF-43BA-9156-6AAD42E6C596}", None, None, None),
+            KnownFolder::CurrentAppMods => ("{3DB40B20-2A30-4DBE-917E-771DD21DD099}", None, None, None),
+            KnownFolder::AppDataDesktop => ("{B2C5E279-7ADD-439F-B28C-C41FE1BBF672}", Some("%LOCALAPPDATA%\\Desktop"), None, None),
+            KnownFolder::AppDataDocuments => ("{7BE16610-1F7F-44AC-BFF0-83E15F2FFCA1}", Some("%LOCALAPPDATA%\\Documents"), None, None),
+            KnownFolder::AppDataFavorites => ("{7CFBEFBC-DE1F-45AA-B843-A542AC536CC9}", Some("%LOCALAPPDATA%\\Favorites"), None, None),
+            KnownFolder::AppDataProgramData => ("{559D40A3-A036-40FA-AF61-84CB430A4D34}", Some("%LOCALAPPDATA%\\ProgramData"), None, None),
+            KnownFolder::LocalStorage => ("{B3EB08D3-A1F3-496B-865A-42B536CDA0EC}", None, None, None)
+        }
+    }
+}
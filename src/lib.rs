@@ -4,6 +4,19 @@ use std::path::PathBuf;
 #[cfg(target_os = "linux")]
 pub mod linux;
 
+// Public as `linux::vdf`, under Windows it is only used to read the steam libraries
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+mod vdf;
+
+#[cfg(target_os = "windows")]
+mod windows;
+
+mod error;
+pub use error::{ProtonFinderError, SearchResult};
+
+mod known_folder;
+pub use known_folder::KnownFolder;
+
 #[cfg(test)]
 pub mod test;
 
@@ -14,7 +27,50 @@ pub mod test;
 /// Under Linux, it will return you the same paths within the prefix as dirs would
 pub struct GameDrive {
     #[cfg(target_os = "linux")]
-    prefix: linux::ProtonPrefix
+    prefix: linux::ProtonPrefix,
+    #[cfg(target_os = "windows")]
+    game_id: u32,
+    #[cfg(target_os = "windows")]
+    create_missing: bool
+}
+
+/// Retrives the abstraction for the access to common folders
+///
+/// Under Windows this will always succeed
+///
+/// Under Linux the user can pass in `$STEAM_DIR` (except if `no_tricks` is set).
+/// If this env is set but the path is invalid/doesn't point to a steam installation, then this is
+/// reported in the warnings, and the search continues through the remaining steam roots.
+/// Search order (the default of `linux::SteamLocator`, see `linux::SteamLocation::DEFAULT_ORDER`):
+/// - $STEAM_DIR (skipped if `no_tricks` or unset)
+/// - ~/.steam/steam/
+/// - ~/.steam/root/
+/// - ~/.local/share/Steam/
+/// - ~/.steam/debian-installation/
+/// - ~/.var/app/com.valvesoftware.Steam/data/Steam/
+/// - ~/snap/steam/common/.local/share/Steam/
+/// - the install noted in ~/.steam/registry.vdf
+///
+/// Under unsupported plattforms this returns Err(UnsupportedPlatform)
+///
+/// So appropriate error handling is to show the warnings to the user (like that the path he defined
+/// in `$STEAM_DIR` is invalid), and then handle the result. An Err(PrefixNotFound) means the game
+/// needs to be installed and launched once for the prefix to exist.
+pub fn locate_game_drive(game_id: u32) -> SearchResult<GameDrive> {
+    #[cfg(target_os = "windows")]
+    {
+        SearchResult { result: Ok(GameDrive { game_id, create_missing: false }), warnings: Vec::new() }
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        linux::locate_prefix(game_id).map(|prefix| GameDrive { prefix })
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+    {
+        SearchResult { result: Err(ProtonFinderError::UnsupportedPlatform), warnings: Vec::new() }
+    }
 }
 
 /// Retrives the abstraction for the access to common folders
@@ -26,11 +82,15 @@ pub struct GameDrive {
 /// function will return an Err (so if `no_tricks` is set, this function can not Err).
 /// Some is returned if the proton prefix was found, but this doesn't have to be from the
 /// `$STEAM_DIR`, as it will search through all till it finds one.
-/// Search order:
+/// Search order (the default of `linux::SteamLocator`, see `linux::SteamLocation::DEFAULT_ORDER`):
 /// - $STEAM_DIR (skipped if `no_tricks` or unset)
 /// - ~/.steam/steam/
-/// - ~/.local/share/steam/
+/// - ~/.steam/root/
+/// - ~/.local/share/Steam/
+/// - ~/.steam/debian-installation/
 /// - ~/.var/app/com.valvesoftware.Steam/data/Steam/
+/// - ~/snap/steam/common/.local/share/Steam/
+/// - the install noted in ~/.steam/registry.vdf
 ///
 /// Under unsupported plattforms this return Ok(None)
 ///
@@ -38,39 +98,44 @@ pub struct GameDrive {
 /// err (that the path he defined in `$STEAM_DIR` is invalid), but then you can continue with the Option.
 /// The Option you handle with an error message and shutdown on none (that the game needs to be
 /// installed and launched once for the prefix to exist)
+#[deprecated(since = "1.2.0", note = "use locate_game_drive, which reports errors as ProtonFinderError")]
 pub fn get_game_drive(game_id: u32) -> Result<Option<GameDrive>, Option<GameDrive>> {
-    #[cfg(target_os = "windows")]
-    {
-        return Ok(Some(GameDrive { }));
-    }
+    locate_game_drive(game_id).into_legacy()
+}
 
-    #[cfg(target_os = "linux")]
-    {
-        let (res, err) = match linux::find_prefix(game_id) {
-            Ok(res) => (res, false),
-            Err(res) => (res, true)
-        };
-
-        let res = if let Some(prefix) = res {
-            Some(GameDrive { prefix })
-        } else {
-            None
-        };
-
-        return match err {
-            false => Ok(res),
-            true => Err(res)
-        };
-    }
-    
+impl GameDrive {
 
-    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
-    {
-        return Ok(None);
+    /// When set, the folder getters create the folder if it does not exist yet,
+    /// so a first time config can be written into it.
+    ///
+    /// Per default missing folders return None (under Linux, see `ProtonPrefix::create_missing`)
+    pub fn create_missing(mut self, enabled: bool) -> Self {
+        #[cfg(target_os = "windows")]
+        {
+            self.create_missing = enabled;
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            self.prefix = self.prefix.create_missing(enabled);
+        }
+
+        #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+        let _ = enabled;
+
+        self
     }
-}
 
-impl GameDrive {
+    /// Creates the folder if `create_missing` is set
+    #[cfg(target_os = "windows")]
+    fn ensure(&self, path: Option<PathBuf>) -> Option<PathBuf> {
+        let path = path?;
+        if self.create_missing && !path.is_dir() {
+            std::fs::create_dir_all(&path).ok()?;
+        }
+
+        Some(path)
+    }
 
     /// Returns the path to the C Drive.
     pub fn c_drive(&self) -> PathBuf {
@@ -79,7 +144,7 @@ impl GameDrive {
 			let mut path = PathBuf::new();
 			path.push(r"C:\");
 			
-			return path;
+			path
 		}
 
         #[cfg(target_os = "linux")]
@@ -90,11 +155,29 @@ impl GameDrive {
         PathBuf::new()
     }
 
+    /// Returns the folder the game is installed in (`steamapps/common/<installdir>`).
+    ///
+    /// This is read from the appmanifest of the game in the steam libraries,
+    /// under Windows the steam install is located through the registry.
+    pub fn install_dir(&self) -> Option<PathBuf> {
+        #[cfg(target_os = "windows")]
+        return windows::install_dir(self.game_id);
+
+        #[cfg(target_os = "linux")]
+        {
+            let roots = linux::locate_all_steam_roots().result.unwrap_or_default();
+            roots.iter().find_map(|root| root.get_install_dir(self.prefix.get_game_id()))
+        }
+
+        #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+        None
+    }
+
     /// This works like `dirs::home_dir` under Windows would
     /// returning `C:\Users\username`.
     pub fn home_dir(&self) -> Option<PathBuf> {
         #[cfg(target_os = "windows")]
-        return dirs::home_dir();
+        return self.ensure(dirs::home_dir());
 
         #[cfg(target_os = "linux")]
         return self.prefix.home_dir();
@@ -108,7 +191,7 @@ impl GameDrive {
     /// returning `C:\Users\username\AppData\Roaming`.
     pub fn config_dir(&self) -> Option<PathBuf> {
         #[cfg(target_os = "windows")]
-        return dirs::config_dir();
+        return self.ensure(dirs::config_dir());
 
         #[cfg(target_os = "linux")]
         return self.prefix.appdata_roaming();
@@ -138,7 +221,7 @@ impl GameDrive {
     /// returning `C:\Users\username\AppData\Local`.
     pub fn config_local_dir(&self) -> Option<PathBuf> {
         #[cfg(target_os = "windows")]
-        return dirs::config_local_dir();
+        return self.ensure(dirs::config_local_dir());
 
         #[cfg(target_os = "linux")]
         return self.prefix.appdata_local();
@@ -171,11 +254,7 @@ impl GameDrive {
             let mut path = dirs::home_dir()?;
             path.push("AppData");
             path.push("LocalLow");
-            if path.is_dir() {
-                return Some(path);
-            } else {
-                return None;
-            }
+            (path.is_dir() || (self.create_missing && std::fs::create_dir_all(&path).is_ok())).then_some(path)
         }
 
         #[cfg(target_os = "linux")]
@@ -189,7 +268,7 @@ impl GameDrive {
     /// returning `C:\Users\username\Music`.
     pub fn audio_dir(&self) -> Option<PathBuf> {
         #[cfg(target_os = "windows")]
-        return dirs::audio_dir();
+        return self.ensure(dirs::audio_dir());
 
         #[cfg(target_os = "linux")]
         return self.prefix.music_dir();
@@ -203,7 +282,7 @@ impl GameDrive {
     /// returning `C:\Users\username\Videos`.
     pub fn video_dir(&self) -> Option<PathBuf> {
         #[cfg(target_os = "windows")]
-        return dirs::video_dir();
+        return self.ensure(dirs::video_dir());
 
         #[cfg(target_os = "linux")]
         return self.prefix.videos_dir();
@@ -217,7 +296,7 @@ impl GameDrive {
     /// returning `C:\Users\username\Pictures`.
     pub fn picture_dir(&self) -> Option<PathBuf> {
         #[cfg(target_os = "windows")]
-        return dirs::picture_dir();
+        return self.ensure(dirs::picture_dir());
 
         #[cfg(target_os = "linux")]
         return self.prefix.picture_dir();
@@ -231,7 +310,7 @@ impl GameDrive {
     /// returning `C:\Users\username\Documents`.
     pub fn document_dir(&self) -> Option<PathBuf> {
         #[cfg(target_os = "windows")]
-        return dirs::document_dir();
+        return self.ensure(dirs::document_dir());
 
         #[cfg(target_os = "linux")]
         return self.prefix.documents_dir();
@@ -245,7 +324,7 @@ impl GameDrive {
     /// returning `C:\Users\username\Downloads`.
     pub fn download_dir(&self) -> Option<PathBuf> {
         #[cfg(target_os = "windows")]
-        return dirs::download_dir();
+        return self.ensure(dirs::download_dir());
 
         #[cfg(target_os = "linux")]
         return self.prefix.downloads_dir();
@@ -259,7 +338,7 @@ impl GameDrive {
     /// returning `C:\Users\username\Desktop`.
     pub fn desktop_dir(&self) -> Option<PathBuf> {
         #[cfg(target_os = "windows")]
-        return dirs::desktop_dir();
+        return self.ensure(dirs::desktop_dir());
 
         #[cfg(target_os = "linux")]
         return self.prefix.desktop_dir();
@@ -269,11 +348,43 @@ impl GameDrive {
         None
     }
 
+    /// Returns the path of a known folder (like `KnownFolder::SavedGames`, `C:\Users\username\Saved Games`).
+    ///
+    /// Under Windows this is `SHGetKnownFolderPath`,
+    /// under Linux it is read from the registry of the prefix (see `ProtonPrefix::known_folder`).
+    /// Virtual folders (like the control panel) return None
+    pub fn known_folder(&self, folder: KnownFolder) -> Option<PathBuf> {
+        #[cfg(target_os = "windows")]
+        return windows::known_folder(folder, self.create_missing);
+
+        #[cfg(target_os = "linux")]
+        return self.prefix.known_folder(folder);
+
+        
+        #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+        None
+    }
+
+    /// Returns the temp folder, `%TEMP%` (usually `C:\Users\username\AppData\Local\Temp`).
+    ///
+    /// This is not a known folder on windows
+    pub fn temp_dir(&self) -> Option<PathBuf> {
+        #[cfg(target_os = "windows")]
+        return self.ensure(Some(std::env::temp_dir()));
+
+        #[cfg(target_os = "linux")]
+        return self.prefix.temp_dir();
+
+        
+        #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+        None
+    }
+
     /// This works like `dirs:public_dir` under Windows would
     /// returning `C:\Users\Public`.
     pub fn public_dir(&self) -> Option<PathBuf> {
         #[cfg(target_os = "windows")]
-        return dirs::public_dir();
+        return self.ensure(dirs::public_dir());
 
         #[cfg(target_os = "linux")]
         return self.prefix.public_user_dir();
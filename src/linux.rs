@@ -1,4 +1,21 @@
-use std::{collections::HashMap, env, ffi::OsString, fs::File, io::{BufRead, BufReader}, path::PathBuf, str::FromStr};
+use std::{cell::OnceCell, collections::{BTreeMap, HashMap}, env, ffi::{OsStr, OsString}, fs, hash::{Hash, Hasher}, os::unix::fs::MetadataExt, path::{Path, PathBuf}, str::FromStr, sync::Arc};
+
+/// Parser and writer for Valve's KeyValues format (vdf), used by most of steams files
+pub mod vdf {
+    pub use crate::vdf::*;
+}
+use crate::{KnownFolder, ProtonFinderError, SearchResult};
+pub use vdf::{parse_vdf_file, parse_vdf_str, try_parse_vdf_file, try_parse_vdf_str, write_vdf_file, VdfAccessError, VdfStruct, VdfValue};
+use vdf::binary::AppInfoEntry;
+
+/// Typed models of steam files, for use with the serde support in `vdf`
+#[cfg(feature = "serde")]
+pub mod models;
+
+/// Types and parsing for wine's registry files
+pub mod registry;
+pub use registry::{Hive, PrefixRegistry, RegKey, RegValue, Registry};
+use registry::HiveCache;
 
 pub const ENV_STEAM_DIR: &str = "STEAM_DIR";
 
@@ -27,30 +44,79 @@ pub fn get_steam_dir_env_value() -> Option<OsString> {
     env::var_os(ENV_STEAM_DIR)
 }
 
+/// Returns the path set in env `$STEAM_DIR`
+///
+/// This will still return the value, even if `no_tricks` is set
+/// If no path was set returns Ok(None)
+/// If the value set was not a valid path (or did not exist) returns Err(InvalidEnvPath)
+pub fn steam_dir_env_path() -> Result<Option<PathBuf>, ProtonFinderError> {
+    if let Some(val) = get_steam_dir_env_value() {
+        let path = PathBuf::from(val);
+        if path.is_dir() {
+            Ok(Some(path))
+        } else {
+            Err(ProtonFinderError::InvalidEnvPath(path))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
 /// Returns the path set in env `$STEAM_DIR`
 ///
 /// This will still return the value, even if `no_tricks` is set
 /// If no path was set returns Err(false)
 /// If the value set was not a valid path (or did not exist) returns Err(true)
+#[deprecated(since = "1.2.0", note = "use steam_dir_env_path, which reports a ProtonFinderError")]
 pub fn get_steam_dir_env_path() -> Result<PathBuf, bool> {
-    if let Some(val) = get_steam_dir_env_value() {
-        if let Ok(path) = PathBuf::try_from(val) {
-            if path.is_dir() {
-                return Ok(path);
-            }
-        }
+    match steam_dir_env_path() {
+        Ok(Some(path)) => Ok(path),
+        Ok(None) => Err(false),
+        Err(_) => Err(true)
+    }
+}
 
-        Err(true)
-    } else {
-        Err(false)
+/// Identifies a folder independent of the path used to reach it (symlinks, bind mounts, etc.)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum FolderId {
+    Inode { dev: u64, ino: u64 },
+    /// Fallback for when the metadata can't be read
+    Path(PathBuf)
+}
+
+impl FolderId {
+    fn of(path: &Path) -> FolderId {
+        match fs::metadata(path) {
+            Ok(meta) => FolderId::Inode { dev: meta.dev(), ino: meta.ino() },
+            Err(_) => FolderId::Path(path.canonicalize().unwrap_or_else(|_| path.to_path_buf()))
+        }
     }
 }
 
 /// An existing steamroot folder with steamapps and steamruntime
+///
+/// Two roots are equal if they are the same folder on disk, even if reached through different
+/// paths (symlinks, bind mounts)
 #[derive(Debug, Clone)]
 pub struct SteamRoot {
     path: PathBuf,
-    steamapps: PathBuf
+    steamapps: PathBuf,
+    source: SteamRootSource,
+    id: FolderId
+}
+
+impl PartialEq for SteamRoot {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for SteamRoot {}
+
+impl Hash for SteamRoot {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
 }
 
 impl SteamRoot {
@@ -58,6 +124,11 @@ impl SteamRoot {
         self.path.clone()
     }
 
+    /// Where this steam root was found
+    pub fn source(&self) -> SteamRootSource {
+        self.source
+    }
+
     /// The steamapps folder in the root directory of steam
     pub fn get_steamapps_folder(&self) -> PathBuf {
         self.steamapps.clone()
@@ -74,13 +145,13 @@ impl SteamRoot {
         let vdf = self.read_library_folders_vdf_file()?;
         let game_id = game_id.to_string();
 
-        for (_,lib) in vdf.pairs.iter() {
+        for (_,lib) in vdf.iter() {
             if let VdfValue::Complex(lib) = lib {
                 
                 // Extracting necessary values
-                if let (Some(VdfValue::Simple(path)),Some(VdfValue::Complex(apps))) = (lib.pairs.get("path"), lib.pairs.get("apps")) {
+                if let (Some(VdfValue::Simple(path)),Some(VdfValue::Complex(apps))) = (lib.get("path"), lib.get("apps")) {
                     
-                    if apps.pairs.contains_key(&game_id) {
+                    if apps.contains_key(&game_id) {
                         // Found the game
                         
                         let buf = PathBuf::from_str(path).ok()?;
@@ -93,6 +164,18 @@ impl SteamRoot {
         None
     }
 
+    /// Returns the folder the game is installed in (`steamapps/common/<installdir>`)
+    ///
+    /// In contrast to `get_install_library` this reads the appmanifest of the game from the
+    /// libraries (starting with the one noted in the libraryfolders.vdf), so it is up to date.
+    pub fn get_install_dir(&self, game_id: u32) -> Option<PathBuf> {
+        if let Some(dir) = self.get_install_library(game_id).and_then(|lib| lib.get_install_dir(game_id)) {
+            return Some(dir);
+        }
+
+        self.get_libraries().iter().find_map(|lib| lib.get_install_dir(game_id))
+    }
+
     /// Attempts to find the prefix for a given game via it's game id
     pub fn get_prefix(&self, game_id: u32) -> Option<ProtonPrefix> {
         if let Some(lib) = self.get_install_library(game_id) {
@@ -122,15 +205,16 @@ impl SteamRoot {
 
         if let Some(vdf) = self.read_library_folders_vdf_file() {
             // Iterating over all entires
-            for (_,lib) in vdf.pairs.iter() {
+            for (_,lib) in vdf.iter() {
                 if let VdfValue::Complex(lib) = lib {
 
                     // retrieving the path for this library
-                    if let Some(VdfValue::Simple(p)) = lib.pairs.get("path") {
+                    if let Some(VdfValue::Simple(p)) = lib.get("path") {
                         
                         // Parsing into wrapper
-                        if let Ok(path) = PathBuf::from_str(p) {
-                            if let Some(item) = SteamLibrary::from_path(&path) {
+                        // The same library can be listed multiple times (through symlinks or bind mounts)
+                        if let Some(item) = SteamLibrary::from_path(&PathBuf::from(p)) {
+                            if !res.contains(&item) {
                                 res.push(item);
                             }
                         }
@@ -142,139 +226,306 @@ impl SteamRoot {
 
         if res.is_empty() {
             // Fallback to garantee at least the root exists
-            res.push(SteamLibrary { steamapps: self.get_steamapps_folder(), is_root: true });
+            res.push(SteamLibrary::new(self.get_steamapps_folder(), true));
         }
 
         res
     }
 
-    /// Reads the libraryfolders file for this streamroot,
-    /// returning on success the contained "libraryfolders" struct (so you can directly access the
-    /// libraries).  
-    ///
-    /// This is in contrast to calling `parse_vdf_file` manually, which would give you the root
-    /// object that contains this struct under said key (so this function here saves you one step).
-    pub fn read_library_folders_vdf_file(&self) -> Option<VdfStruct> {
-        let mut path = self.get_steamapps_folder();
-        path.push("libraryfolders.vdf");
-
-        // We use remove here to avoid a clone call
-        let mut vdf = parse_vdf_file(&path)?;
-        if let Some(VdfValue::Complex(res)) = vdf.pairs.remove("libraryfolders") {
-            Some(res)
-        } else {
-            None
+    /// Returns the steam accounts that logged in on this install, as noted in `config/loginusers.vdf`,
+    /// starting with the most recent one
+    pub fn users(&self) -> Vec<SteamUser> {
+        let mut path = self.get_root();
+        path.push("config");
+        path.push("loginusers.vdf");
+
+        let mut res = Vec::new();
+        if let Some(users) = parse_vdf_file(&path).as_ref().and_then(|vdf| vdf.get_ignore_case("users")?.as_struct().ok()) {
+            for (id, user) in users.iter() {
+                if let (Ok(steam_id), Ok(user)) = (id.parse(), user.as_struct()) {
+                    if let Some(user) = SteamUser::from_vdf(self.get_root(), steam_id, user) {
+                        res.push(user);
+                    }
+                }
+            }
         }
+
+        res.sort_by_key(|user| (!user.most_recent, std::cmp::Reverse(user.timestamp)));
+        res
     }
-}
 
-/// Parses a vdf file at the given location
-///
-/// This was made to parse the libraryfolders.vdf,
-/// so other vdf files might not get properly parsed
-pub fn parse_vdf_file(file_path: &PathBuf) -> Option<VdfStruct> {
+    /// Returns where the Steam Auto-Cloud of the game syncs the save files from, as noted in the
+    /// `ufs` section of the game in `appcache/appinfo.vdf`.
+    ///
+    /// Windows roots (like `WinAppDataLocal`) are resolved within the prefix of the game,
+    /// Linux roots (only present for native games, or through a `rootoverride`) within the home folder,
+    /// and `SteamCloudDocuments` to `~/.SteamCloud/<account name>/<install folder>`.
+    /// Locations whose root can not be resolved (like Mac roots, or no prefix exists) are still
+    /// returned, with `dir` set to None.
+    /// `{64BitSteamID}` and `{Steam3AccountID}` in the path are filled in with the most recent user.
+    ///
+    /// The folders may not exist yet, if the game never saved
+    pub fn cloud_save_locations(&self, game_id: u32) -> Vec<CloudSaveLocation> {
+        let Some(entry) = self.read_appinfo_entries(&[game_id]).pop() else {
+            return Vec::new();
+        };
+        let Some(ufs) = entry.data.get_path("appinfo/ufs").and_then(VdfValue::as_struct).ok() else {
+            return Vec::new();
+        };
 
-    // Parses structs recusrively
-    fn parse_struct(reader: &mut BufReader<File>, root: bool) -> Option<VdfStruct> {
-        let mut obj = VdfStruct { pairs: HashMap::new() };
-        
-        let mut line = String::new();
-        while let Ok(length) = reader.read_line(&mut line) {
-            // EOF detection
-            if length == 0 {
-                if root {
-                    return Some(obj);
-                } else {
-                    return None;
+        let prefix = self.get_prefix(game_id);
+        let user = self.users().into_iter().next();
+        let mut res = Vec::new();
+        for file in ufs_entries(ufs, "savefiles") {
+            let text = |key: &str| file.get_ignore_case(key).and_then(|val| val.as_str().ok()).unwrap_or_default().to_string();
+            let platforms = file.get_ignore_case("platforms").and_then(|val| val.as_struct().ok())
+                .map(|platforms| platforms.iter().filter_map(|(_, val)| val.as_str().ok()).collect::<Vec<_>>())
+                .unwrap_or_default();
+            let location = UfsLocation {
+                root: text("root"),
+                path: text("path"),
+                pattern: text("pattern"),
+                recursive: file.get_ignore_case("recursive").and_then(|val| val.as_bool().ok()).unwrap_or_default()
+            };
+
+            // The game runs either as the windows version through proton, or natively
+            let any = platforms.is_empty() || platforms.iter().any(|os| os.eq_ignore_ascii_case("all"));
+            let mut locations = Vec::new();
+            if any || platforms.iter().any(|os| os.eq_ignore_ascii_case("windows")) {
+                locations.push(location.clone());
+            }
+            if any || platforms.iter().any(|os| os.eq_ignore_ascii_case("linux")) {
+                let overridden: Vec<UfsLocation> = ufs_entries(ufs, "rootoverrides").filter_map(|over| location.apply_override(over, "linux")).collect();
+                match overridden.is_empty() {
+                    true => locations.push(location.clone()),
+                    false => locations.extend(overridden)
                 }
             }
 
-            let trimed = line.trim();
-            
-            if trimed == "}" {
-                // Object ended
-                return Some(obj);
+            for location in locations {
+                let location = self.resolve_ufs_location(location, game_id, prefix.as_ref(), user.as_ref());
+                if !res.contains(&location) {
+                    res.push(location);
+                }
             }
+        }
 
-            // Key parsing
-            if let Some(part) = trimed.strip_prefix('"') {
-                let (key, part) = part.split_once('"')?;
-
-                let part_trimed = part.trim();
-
-                let (key, value) = if let Some((_,val_untrimmed)) = part_trimed.split_once('"') {
-                    // This handles simpletype
-                    let (val,_) = val_untrimmed.split_once('"')?;
-                    (key.to_string(), VdfValue::Simple(val.to_string()))
-                } else {
-                    // This handles complextype by reading another line to find the bracket
-                    // open, and then do recursion
-                    let key = key.to_string();
-
-                    line.clear();
-                    if reader.read_line(&mut line).ok()? == 0 {
-                        // EOF between the key and the struct
-                        return None;
-                    }
+        res
+    }
 
-                    let trimed = line.trim();
-                    if trimed != "{" {
-                        // Unexpected symbol
-                        return None;
-                    }
+    fn resolve_ufs_location(&self, location: UfsLocation, game_id: u32, prefix: Option<&ProtonPrefix>, user: Option<&SteamUser>) -> CloudSaveLocation {
+        let dir = self.resolve_ufs_dir(&location, game_id, prefix, user);
+        CloudSaveLocation { root: location.root, dir, pattern: location.pattern, recursive: location.recursive }
+    }
 
-                    let s = parse_struct(reader, false)?;
-                    (key, VdfValue::Complex(s))
-                };
+    fn resolve_ufs_dir(&self, location: &UfsLocation, game_id: u32, prefix: Option<&ProtonPrefix>, user: Option<&SteamUser>) -> Option<PathBuf> {
+        let mut dir = match location.root.to_ascii_lowercase().as_str() {
+            "gameinstall" => self.get_install_dir(game_id)?,
+            "steamclouddocuments" => {
+                let install_dir = self.get_install_dir(game_id)?;
+                let account_name = Some(user?.account_name.as_str()).filter(|name| !name.is_empty())?;
+                dirs::home_dir()?.join(".SteamCloud").join(account_name).join(install_dir.file_name()?)
+            },
+            "windowshome" => prefix?.home_dir()?,
+            "winmydocuments" => prefix?.documents_dir()?,
+            "winappdatalocal" => prefix?.appdata_local()?,
+            "winappdatalocallow" => prefix?.appdata_local_low()?,
+            "winappdataroaming" => prefix?.appdata_roaming()?,
+            "winsavedgames" => prefix?.known_folder(KnownFolder::SavedGames)?,
+            "linuxhome" => dirs::home_dir()?,
+            "linuxxdgdatahome" => dirs::data_dir()?,
+            "linuxxdgconfighome" => dirs::config_dir()?,
+            // The Mac roots (MacHome, MacAppSupport, MacDocuments) don't exist here
+            _ => return None
+        };
+
+        let mut path = location.path.clone();
+        for (var, value) in [("{64BitSteamID}", user.map(|user| user.steam_id)), ("{Steam3AccountID}", user.map(|user| user.account_id.into()))] {
+            if path.contains(var) {
+                path = path.replace(var, &value?.to_string());
+            }
+        }
+
+        for part in path.split(['\\', '/']).filter(|part| !part.is_empty() && *part != ".") {
+            dir = find_entry_ignore_case(&dir, part).unwrap_or_else(|| dir.join(part));
+        }
+
+        Some(dir)
+    }
+
+    /// Returns the compatibility tool (like Proton) steam runs the game with, as set in
+    /// `CompatToolMapping` within `config/config.vdf`.
+    /// If the game has no mapping the global default (app 0) is used.
+    ///
+    /// Returns None if neither is set, so the game runs natively.
+    /// Steam only applies the default to games without a Linux version, so for games whose
+    /// `oslist` in `appcache/appinfo.vdf` contains linux None is returned as well.
+    /// If the game is missing in the appinfo the default is assumed to apply (`is_default` is set).
+    pub fn compat_tool_for(&self, game_id: u32) -> Option<CompatTool> {
+        // Only read when needed, but then only once for both apps
+        let appinfo = OnceCell::new();
+        let appinfo = || appinfo.get_or_init(|| self.read_appinfo_entries(&[game_id, STEAM_PLAY_MANIFESTS_APP_ID])).as_slice();
+
+        let mut path = self.get_root();
+        path.push("config");
+        path.push("config.vdf");
+
+        let vdf = parse_vdf_file(&path)?;
+        let mapping = ["InstallConfigStore", "Software", "Valve", "Steam", "CompatToolMapping"].iter()
+            .try_fold(&vdf, |vdf, key| vdf.get_ignore_case(key)?.as_struct().ok())?;
+        let tool_name = |id: u32| {
+            let name = mapping.get(&id.to_string())?.as_struct().ok()?.get_ignore_case("name")?.as_str().ok()?;
+            Some(name.to_string()).filter(|name| !name.is_empty())
+        };
+
+        let (name, is_default) = match tool_name(game_id) {
+            Some(name) => (name, false),
+            None if has_linux_version(appinfo(), game_id) => return None,
+            None => (tool_name(0)?, true)
+        };
+
+        let (install_dir, display_name) = match self.find_custom_compat_tool(&name) {
+            Some((dir, display_name)) => (Some(dir), display_name),
+            None => self.find_official_compat_tool(appinfo(), &name).unwrap_or((None, name.clone()))
+        };
+
+        let compatdata = self.get_prefix(game_id).and_then(|pfx| pfx.get_pfx_path().parent().map(Path::to_path_buf));
+        let read = |file: &str| compatdata.as_ref().and_then(|dir| fs::read_to_string(dir.join(file)).ok());
+        let config_info = read("config_info");
+        let config_lines = config_info.as_deref().map(|info| info.lines().map(str::trim).collect::<Vec<_>>()).unwrap_or_default();
+
+        Some(CompatTool {
+            name,
+            display_name,
+            install_dir,
+            is_default,
+            prefix_version: read("version").map(|version| version.trim().to_string())
+                .or_else(|| config_lines.first().map(|line| line.to_string()))
+                .filter(|version| !version.is_empty()),
+            // The other lines are files of the tool (like its fonts), within the `files` or `dist` folder
+            prefix_tool_dir: config_lines.iter().skip(1).find_map(|line| {
+                let path = Path::new(line);
+                path.ancestors().find(|dir| matches!(dir.file_name().and_then(|name| name.to_str()), Some("files" | "dist")))?
+                    .parent().map(Path::to_path_buf)
+            })
+        })
+    }
+
+    /// Reads the entries of these apps from `appcache/appinfo.vdf`, this file is commonly
+    /// hundreds of MB, so all apps needed should be read at once
+    fn read_appinfo_entries(&self, app_ids: &[u32]) -> Vec<AppInfoEntry> {
+        let mut path = self.get_root();
+        path.push("appcache");
+        path.push("appinfo.vdf");
 
-                obj.pairs.insert(key, value);
-            } else if !trimed.is_empty() {
-                return None;
+        vdf::binary::read_appinfo_entries(&path, app_ids)
+    }
+
+    /// Searches the `compatibilitytools.d` folders for a tool with this name,
+    /// returning it's install dir and display name
+    fn find_custom_compat_tool(&self, name: &str) -> Option<(PathBuf, String)> {
+        let mut dirs = vec![self.get_root().join(COMPAT_TOOLS_DIR)];
+        if cfg!(not(feature = "no_tricks")) {
+            if let Some(extra) = env::var_os(STEAM_EXTRA_COMPAT_TOOLS_PATHS) {
+                dirs.extend(env::split_paths(&extra));
+            }
+        }
+        dirs.extend(COMPAT_TOOLS_SYSTEM_DIRS.iter().map(PathBuf::from));
+
+        for tool_dir in dirs.iter().flat_map(|dir| dir.read_dir().into_iter().flatten().flatten()) {
+            let tool_dir = tool_dir.path();
+            let Some(vdf) = parse_vdf_file(&tool_dir.join("compatibilitytool.vdf")) else {
+                continue;
             };
 
-            line.clear();
+            let tool = vdf.get_path("compatibilitytools/compat_tools").and_then(VdfValue::as_struct).ok()
+                .and_then(|tools| tools.get(name)?.as_struct().ok());
+            if let Some(tool) = tool {
+                let install_path = tool.get_ignore_case("install_path").and_then(|val| val.as_str().ok()).unwrap_or(".");
+                let display_name = tool.get_ignore_case("display_name").and_then(|val| val.as_str().ok()).unwrap_or(name);
+                let dir = tool_dir.join(install_path);
+                return Some((dir.canonicalize().unwrap_or(dir), display_name.to_string()));
+            }
         }
 
         None
     }
 
+    /// Looks up a tool distributed through steam (like `proton_experimental`) in the appinfo of
+    /// the SteamPlay manifests, returning it's install dir (if installed) and display name
+    fn find_official_compat_tool(&self, appinfo: &[AppInfoEntry], name: &str) -> Option<(Option<PathBuf>, String)> {
+        let entry = appinfo.iter().find(|entry| entry.app_id == STEAM_PLAY_MANIFESTS_APP_ID)?;
+        let info = entry.data.get_path("appinfo").and_then(VdfValue::as_struct).ok()?;
+        let tools = ["extended/compat_tools", "compat_tools"].iter().find_map(|path| info.get_path(path).and_then(VdfValue::as_struct).ok())?;
+        let tool = tools.get(name)?.as_struct().ok()?;
+
+        let app_id = tool.get_ignore_case("appid").and_then(|val| val.as_u64().ok()).and_then(|id| u32::try_from(id).ok());
+        let install_dir = app_id.and_then(|id| self.get_install_dir(id));
+        let display_name = tool.get_ignore_case("display_name").and_then(|val| val.as_str().ok())
+            .map(str::to_string)
+            .or_else(|| app_id.and_then(|id| self.get_install_library(id)?.get_app_manifest(id)).map(|manifest| manifest.name))
+            .unwrap_or_else(|| name.to_string());
+
+        Some((install_dir, display_name))
+    }
 
-    let file = File::open(file_path).ok()?;
-    let mut reader = BufReader::new(file);
+    /// Reads the libraryfolders file for this streamroot,
+    /// returning on success the contained "libraryfolders" struct (so you can directly access the
+    /// libraries).  
+    ///
+    /// This is in contrast to calling `parse_vdf_file` manually, which would give you the root
+    /// object that contains this struct under said key (so this function here saves you one step).
+    pub fn read_library_folders_vdf_file(&self) -> Option<VdfStruct> {
+        let mut path = self.get_steamapps_folder();
+        path.push("libraryfolders.vdf");
 
-    parse_struct(&mut reader, true)
+        // We use remove here to avoid a clone call
+        let mut vdf = parse_vdf_file(&path)?;
+        if let Some(VdfValue::Complex(res)) = vdf.remove("libraryfolders") {
+            Some(res)
+        } else {
+            None
+        }
+    }
 }
 
-/// Represents a Vdf Complextype with multiple key value pairs, where the value can be further nested structs
+/// Wrapper around a SteamLibrary with a compatdata folder
+///
+/// Two libraries are equal if their steamapps are the same folder on disk, even if reached through
+/// different paths (symlinks, bind mounts)
 #[derive(Debug, Clone)]
-pub struct VdfStruct {
-    pub pairs: HashMap<String, VdfValue>
+pub struct SteamLibrary {
+    steamapps: PathBuf,
+    is_root: bool,
+    id: FolderId
 }
 
-/// Represents the two value types for vdf:
-/// - Simpletype, which is a String value on the same line as it's key
-/// - Complextype, which is a struct started with { and ended with } on seperate lines
-#[derive(Debug, Clone)]
-pub enum VdfValue {
-    Complex(VdfStruct),
-    Simple(String)
+impl PartialEq for SteamLibrary {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
 }
 
+impl Eq for SteamLibrary {}
 
-/// Wrapper around a SteamLibrary with a compatdata folder
-#[derive(Debug, Clone)]
-pub struct SteamLibrary {
-    steamapps: PathBuf,
-    is_root: bool
+impl Hash for SteamLibrary {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
 }
 
 
 impl SteamLibrary {
+    fn new(steamapps: PathBuf, is_root: bool) -> Self {
+        let id = FolderId::of(&steamapps);
+        Self { steamapps, is_root, id }
+    }
+
     /// Produces a new wrapper for the given location, as long as a compatdata folder is present
     ///
     /// Important: You are passing in the library folder, as set in steam, not the contained
     /// steamapps folder!
-    pub fn from_path(lib: &PathBuf) -> Option<Self> {
+    pub fn from_path(lib: &Path) -> Option<Self> {
         let mut apps = has_steamapps(lib)?;
         apps.push("compatdata");
         if !apps.exists() {
@@ -282,7 +533,7 @@ impl SteamLibrary {
         }
         apps.pop();
         
-        Some(Self { steamapps: apps, is_root: has_runtime(lib) })
+        Some(Self::new(apps, has_runtime(lib)))
     }
 
     /// Attempts to find the prefix for a given game via it's game id.  
@@ -290,7 +541,7 @@ impl SteamLibrary {
     /// This only checks if there is a prefix for the game in THIS library, so:  
     /// - The game might be installed here, but the prefix is left in the root (Steamdeck SD-Card behavior)
     /// - There is leftover data from the game being here that has not been cleaned up (you get a
-    /// prefix then, but you shouldn't use it, as it is irrelevant to the current install of the game)
+    ///   prefix then, but you shouldn't use it, as it is irrelevant to the current install of the game)
     /// - The game is in another library (then you need to check the other Libaries).
     /// 
     /// In general, it is better to just SteamRoot, as this compensates for these anomalies
@@ -329,11 +580,320 @@ impl SteamLibrary {
     pub fn is_root(&self) -> bool {
         self.is_root
     }
+
+    /// Reads the appmanifest of the game from this library.
+    ///
+    /// Steam writes an `appmanifest_<game_id>.acf` for every app in the library it is installed
+    /// in, so if this returns None the game is not installed here.
+    pub fn get_app_manifest(&self, game_id: u32) -> Option<AppManifest> {
+        let mut path = self.get_steamapps_folder();
+        path.push(format!("appmanifest_{}.acf", game_id));
+
+        AppManifest::from_file(&path)
+    }
+
+    /// Returns the folder the game is installed in, if it is installed in this library
+    ///
+    /// The folder name from the appmanifest is matched ignoring case, as it does not always match
+    /// the case of the folder on disk
+    pub fn get_install_dir(&self, game_id: u32) -> Option<PathBuf> {
+        let manifest = self.get_app_manifest(game_id)?;
+        let common = find_dir_ignore_case(&self.steamapps, "common")?;
+
+        find_dir_ignore_case(&common, &manifest.install_dir)
+    }
+
+    /// Returns the appmanifests of all apps installed in this library, sorted by their id
+    pub fn installed_apps(&self) -> Vec<AppManifest> {
+        let mut res = Vec::<AppManifest>::new();
+
+        if let Ok(iter) = self.steamapps.read_dir() {
+            for item in iter.flatten() {
+                let name = item.file_name();
+                let is_manifest = name.to_str().is_some_and(|name| name.starts_with("appmanifest_") && name.ends_with(".acf"));
+
+                if is_manifest {
+                    if let Some(manifest) = AppManifest::from_file(&item.path()) {
+                        res.push(manifest);
+                    }
+                }
+            }
+        }
+
+        res.sort_by_key(|manifest| manifest.app_id);
+        res
+    }
+}
+
+/// The SteamID64 of the first individual account, the account id is the offset from it
+const STEAM_ID64_BASE: u64 = 76561197960265728;
+
+/// The app id of the screenshots, whose cloud folder contains the screenshots of all games
+const SCREENSHOTS_APP_ID: u32 = 760;
+
+/// A steam account that logged in on this install, as read from `config/loginusers.vdf`
+#[derive(Debug, Clone)]
+pub struct SteamUser {
+    /// The SteamID64, like `76561197960287930`
+    pub steam_id: u64,
+    /// The id used for the folder in `userdata` (and in friend codes)
+    pub account_id: u32,
+    /// The name used to log in
+    pub account_name: String,
+    /// The display name
+    pub persona_name: String,
+    /// If this is the account that was logged in last
+    pub most_recent: bool,
+    /// Unix timestamp of the last login
+    pub timestamp: u64,
+    steam_root: PathBuf
+}
+
+impl SteamUser {
+    fn from_vdf(steam_root: PathBuf, steam_id: u64, vdf: &VdfStruct) -> Option<SteamUser> {
+        let text = |key: &str| vdf.get_ignore_case(key).and_then(|val| val.as_str().ok()).unwrap_or_default().to_string();
+
+        Some(SteamUser {
+            steam_id,
+            account_id: u32::try_from(steam_id.checked_sub(STEAM_ID64_BASE)?).ok()?,
+            account_name: text("AccountName"),
+            persona_name: text("PersonaName"),
+            most_recent: vdf.get_ignore_case("MostRecent").and_then(|val| val.as_bool().ok()).unwrap_or_default(),
+            timestamp: vdf.get_ignore_case("Timestamp").and_then(|val| val.as_u64().ok()).unwrap_or_default(),
+            steam_root
+        })
+    }
+
+    /// Returns the folder of this user within `userdata`, if it exists
+    pub fn userdata_dir(&self) -> Option<PathBuf> {
+        let mut path = self.steam_root.clone();
+        path.push("userdata");
+        path.push(self.account_id.to_string());
+
+        if path.is_dir() {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the folder the steam cloud saves of the game are synced to,
+    /// `userdata/<account_id>/<game_id>/remote`
+    pub fn app_remote_dir(&self, game_id: u32) -> Option<PathBuf> {
+        let mut path = self.userdata_dir()?;
+        path.push(game_id.to_string());
+        path.push("remote");
+
+        if path.is_dir() {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the folder the screenshots of the game taken through steam are stored in,
+    /// `userdata/<account_id>/760/remote/<game_id>/screenshots`
+    pub fn screenshots_dir(&self, game_id: u32) -> Option<PathBuf> {
+        let mut path = self.app_remote_dir(SCREENSHOTS_APP_ID)?;
+        path.push(game_id.to_string());
+        path.push("screenshots");
+
+        if path.is_dir() {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    /// Reads `userdata/<account_id>/config/localconfig.vdf`,
+    /// returning on success the contained "UserLocalConfigStore" struct
+    pub fn read_local_config_vdf_file(&self) -> Option<VdfStruct> {
+        let mut path = self.userdata_dir()?;
+        path.push("config");
+        path.push("localconfig.vdf");
+
+        let mut vdf = parse_vdf_file(&path)?;
+        if let Some(VdfValue::Complex(res)) = vdf.remove("UserLocalConfigStore") {
+            Some(res)
+        } else {
+            None
+        }
+    }
+}
+
+/// A folder the Steam Auto-Cloud syncs save files from, see `SteamRoot::cloud_save_locations`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloudSaveLocation {
+    /// The root the path is relative to, like `WinAppDataLocal` or `gameinstall`
+    /// (after a `rootoverride` was applied)
+    pub root: String,
+    /// The folder the save files are in, None if the root can not be resolved on this system
+    /// (like the Mac roots, or a Windows root for a game without prefix)
+    pub dir: Option<PathBuf>,
+    /// The pattern the file names have to match, like `*.sav`
+    pub pattern: String,
+    /// If the files in subfolders of `dir` are synced too
+    pub recursive: bool
+}
+
+/// A `savefiles` entry of the `ufs` section, before the root is resolved
+#[derive(Debug, Clone)]
+struct UfsLocation {
+    root: String,
+    path: String,
+    pattern: String,
+    recursive: bool
+}
+
+impl UfsLocation {
+    /// Applies the `rootoverrides` entry if it is for this root and matches the os
+    fn apply_override(&self, over: &VdfStruct, os: &str) -> Option<UfsLocation> {
+        let text = |key: &str| over.get_ignore_case(key).and_then(|val| val.as_str().ok()).unwrap_or_default();
+
+        let matches = text("os").eq_ignore_ascii_case(os);
+        let applies = match text("oscompare") {
+            "!=" => !matches,
+            _ => matches
+        };
+        if !text("root").eq_ignore_ascii_case(&self.root) || !applies || text("useinstead").is_empty() {
+            return None;
+        }
+
+        let mut path = self.path.clone();
+        for transform in ufs_entries(over, "pathtransforms") {
+            let find = transform.get_ignore_case("find").and_then(|val| val.as_str().ok()).unwrap_or_default();
+            if !find.is_empty() {
+                let replace = transform.get_ignore_case("replace").and_then(|val| val.as_str().ok()).unwrap_or_default();
+                path = path.replace(find, replace);
+            }
+        }
+
+        let add = text("addpath");
+        if !add.is_empty() {
+            path = format!("{}/{}", add, path);
+        }
+
+        Some(UfsLocation { root: text("useinstead").to_string(), path, pattern: self.pattern.clone(), recursive: self.recursive })
+    }
+}
+
+/// Iterates over the numbered entries of a list within the `ufs` section (like `savefiles`)
+fn ufs_entries<'a>(ufs: &'a VdfStruct, key: &str) -> impl Iterator<Item = &'a VdfStruct> + 'a {
+    ufs.get_ignore_case(key).and_then(|val| val.as_struct().ok()).into_iter()
+        .flat_map(|list| list.iter().filter_map(|(_, val)| val.as_struct().ok()))
+}
+
+/// If the `oslist` of the game in the appinfo contains linux, so it runs natively
+fn has_linux_version(appinfo: &[AppInfoEntry], game_id: u32) -> bool {
+    appinfo.iter().find(|entry| entry.app_id == game_id)
+        .and_then(|entry| entry.data.get_path("appinfo/common/oslist").and_then(VdfValue::as_str).ok())
+        .is_some_and(|oslist| oslist.split(',').any(|os| os.trim().eq_ignore_ascii_case("linux")))
+}
+
+/// Folder within the steam root (and the system folders) containing custom compatibility tools like GE-Proton
+const COMPAT_TOOLS_DIR: &str = "compatibilitytools.d";
+const COMPAT_TOOLS_SYSTEM_DIRS: [&str; 2] = ["/usr/share/steam/compatibilitytools.d", "/usr/local/share/steam/compatibilitytools.d"];
+/// Env value with additional folders containing compatibility tools, separated by `:`
+const STEAM_EXTRA_COMPAT_TOOLS_PATHS: &str = "STEAM_EXTRA_COMPAT_TOOLS_PATHS";
+
+/// The app whose appinfo lists the compatibility tools distributed through steam
+const STEAM_PLAY_MANIFESTS_APP_ID: u32 = 891390;
+
+/// The compatibility tool a game is run with, see `SteamRoot::compat_tool_for`
+#[derive(Debug, Clone)]
+pub struct CompatTool {
+    /// The internal name, like `proton_experimental` or `GE-Proton9-1`
+    pub name: String,
+    /// The name shown in steam, like `Proton Experimental`
+    pub display_name: String,
+    /// The folder the tool is installed in, None if it is not installed (or could not be found)
+    pub install_dir: Option<PathBuf>,
+    /// If this is the default for all games (app 0), instead of being set for this game
+    pub is_default: bool,
+    /// The version of the tool that last ran the prefix, like `9.0-100`
+    /// (read from the `version` or `config_info` file in `compatdata/<game_id>`)
+    pub prefix_version: Option<String>,
+    /// The folder of the tool that last ran the prefix, as noted in the `config_info` file
+    pub prefix_tool_dir: Option<PathBuf>
+}
+
+/// Flag within `StateFlags` that is set once the app is completely installed
+const STATE_FULLY_INSTALLED: u32 = 4;
+
+/// The metadata of an installed game, as read from it's `appmanifest_<game_id>.acf`
+#[derive(Debug, Clone)]
+pub struct AppManifest {
+    pub app_id: u32,
+    pub name: String,
+    /// Name of the folder within `steamapps/common` the game is installed in
+    pub install_dir: String,
+    pub build_id: u64,
+    /// Bitflags of the install state (like updating, fully installed, etc.)
+    pub state_flags: u32,
+    pub size_on_disk: u64,
+    /// Unix timestamp of the last update
+    pub last_updated: u64,
+    /// The installed depots, keyed by their depot id
+    pub installed_depots: BTreeMap<u32, InstalledDepot>
+}
+
+/// A depot (part of the game files) installed for an app
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InstalledDepot {
+    pub manifest: u64,
+    pub size: u64,
+    /// Set if this depot belongs to a DLC
+    #[cfg_attr(feature = "serde", serde(rename = "dlcappid", default))]
+    pub dlc_app_id: Option<u32>
+}
+
+impl AppManifest {
+    /// Parses an appmanifest file at the given location
+    pub fn from_file(file_path: &Path) -> Option<AppManifest> {
+        Self::from_vdf(&parse_vdf_file(file_path)?)
+    }
+
+    /// Reads the manifest from the parsed vdf of the file (so with the `AppState` struct at the root)
+    pub fn from_vdf(vdf: &VdfStruct) -> Option<AppManifest> {
+        let state = vdf.get_path("AppState").and_then(VdfValue::as_struct).ok()?;
+
+        let number = |key: &str| state.get_path(key).and_then(VdfValue::as_u64).unwrap_or_default();
+
+        let mut installed_depots = BTreeMap::new();
+        if let Ok(depots) = state.get_path("InstalledDepots").and_then(VdfValue::as_struct) {
+            for (id, depot) in depots.iter() {
+                if let (Ok(depot_id), Ok(depot)) = (id.parse(), depot.as_struct()) {
+                    installed_depots.insert(depot_id, InstalledDepot {
+                        manifest: depot.get_path("manifest").and_then(VdfValue::as_u64).unwrap_or_default(),
+                        size: depot.get_path("size").and_then(VdfValue::as_u64).unwrap_or_default(),
+                        dlc_app_id: depot.get_path("dlcappid").and_then(VdfValue::as_u64).ok().and_then(|id| u32::try_from(id).ok())
+                    });
+                }
+            }
+        }
+
+        Some(AppManifest {
+            app_id: u32::try_from(state.get_path("appid").and_then(VdfValue::as_u64).ok()?).ok()?,
+            name: state.get_path("name").and_then(VdfValue::as_str).unwrap_or_default().to_string(),
+            install_dir: state.get_path("installdir").and_then(VdfValue::as_str).ok()?.to_string(),
+            build_id: number("buildid"),
+            state_flags: u32::try_from(number("StateFlags")).unwrap_or_default(),
+            size_on_disk: number("SizeOnDisk"),
+            last_updated: number("LastUpdated"),
+            installed_depots
+        })
+    }
+
+    /// If the game is completely installed (so not still downloading or uninstalled)
+    pub fn is_fully_installed(&self) -> bool {
+        self.state_flags & STATE_FULLY_INSTALLED != 0
+    }
 }
 
 
-fn has_runtime(steam_root: &PathBuf) -> bool {
-    let mut steam_runtime = steam_root.clone();
+fn has_runtime(steam_root: &Path) -> bool {
+    let mut steam_runtime = steam_root.to_path_buf();
     steam_runtime.push("ubuntu12_32");
 
     if steam_runtime.is_dir() {
@@ -347,179 +907,481 @@ fn has_runtime(steam_root: &PathBuf) -> bool {
     steam_runtime.is_dir()
 }
 
-fn has_steamapps(steam_root: &PathBuf) -> Option<PathBuf> {
+fn has_steamapps(steam_root: &Path) -> Option<PathBuf> {
     // any spelling of steamapps is apparently valid, so we have to check all folders
-    if let Ok(mut iter) = steam_root.read_dir() {
-        while let Some(Ok(item)) = iter.next() {
-            if item.file_name().to_ascii_lowercase() == OsString::from_str("steamapps").expect("valid os string") && item.path().is_dir() {
-                return Some(item.path());
-            }
-        }
+    find_dir_ignore_case(steam_root, "steamapps")
+}
+
+/// Returns the folder within parent that matches the name, ignoring case
+fn find_dir_ignore_case(parent: &Path, name: &str) -> Option<PathBuf> {
+    parent.read_dir().ok()?.flatten()
+        .find(|item| name_eq_ignore_case(&item.file_name(), name) && item.path().is_dir())
+        .map(|item| item.path())
+}
+
+/// Returns the file or folder within parent that matches the name, preferring an exact match
+/// over one ignoring case
+fn find_entry_ignore_case(parent: &Path, name: &str) -> Option<PathBuf> {
+    let path = parent.join(name);
+    if path.exists() {
+        return Some(path);
     }
 
-    None
+    parent.read_dir().ok()?.flatten()
+        .find(|item| name_eq_ignore_case(&item.file_name(), name))
+        .map(|item| item.path())
+}
+
+/// Compares a name on disk with the one searched for, ignoring case the same way as the registry
+/// lookups (names that are not valid UTF-8 never match)
+fn name_eq_ignore_case(entry: &OsStr, name: &str) -> bool {
+    entry.to_str().is_some_and(|entry| registry::eq_ignore_case(entry, name))
 }
 
 /// This verifies that at a given path exists a steam root folder
+///
+/// The source of the returned root is `SteamRootSource::Custom`
 pub fn steam_root_from(path: PathBuf) -> Option<SteamRoot> {
+    steam_root_with_source(path, SteamRootSource::Custom)
+}
+
+fn steam_root_with_source(path: PathBuf, source: SteamRootSource) -> Option<SteamRoot> {
     if !path.is_dir() {
         return None;
     }
     
     if has_runtime(&path) {
         if let Some(apps) = has_steamapps(&path) {
-            return Some(SteamRoot { path, steamapps: apps });
+            let id = FolderId::of(&path);
+            return Some(SteamRoot { path, steamapps: apps, source, id });
         }
     }
 
     None
 }
 
+/// Returns the steam root from the path set in env `$STEAM_DIR`
+///
+/// This will still return the value, even if `no_tricks` is set
+/// If no path was set returns Ok(None)
+/// If the value set was not a valid path (or not a steam root) returns Err(InvalidEnvPath)
+pub fn steam_root_from_env() -> Result<Option<SteamRoot>, ProtonFinderError> {
+    match steam_dir_env_path()? {
+        Some(path) => match steam_root_with_source(path.clone(), SteamRootSource::Env) {
+            Some(root) => Ok(Some(root)),
+            None => Err(ProtonFinderError::InvalidEnvPath(path))
+        },
+        None => Ok(None)
+    }
+}
+
 /// Returns the steam root from the path set in env `$STEAM_DIR`
 ///
 /// This will still return the value, even if `no_tricks` is set
 /// If no path was set returns Err(false)
 /// If the value set was not a valid path (or did not exist) returns Err(true)
+#[deprecated(since = "1.2.0", note = "use steam_root_from_env, which reports a ProtonFinderError")]
 pub fn steam_root_env() -> Result<SteamRoot, bool> {
-    steam_root_from(get_steam_dir_env_path()?).ok_or(true)
+    match steam_root_from_env() {
+        Ok(Some(root)) => Ok(root),
+        Ok(None) => Err(false),
+        Err(_) => Err(true)
+    }
 }
 
 // Common Steam Paths
 const STEAM_DOT_STEAM: &str = "~/.steam/steam";
+const STEAM_DOT_STEAM_ROOT: &str = "~/.steam/root";
 const STEAM_LOCAL_SHARE: &str = "~/.local/share/Steam";
+const STEAM_DEBIAN: &str = "~/.steam/debian-installation";
 const STEAM_FLATPAK: &str = "~/.var/app/com.valvesoftware.Steam/data/Steam/";
+const STEAM_SNAP: &str = "~/snap/steam/common/.local/share/Steam";
+const STEAM_REGISTRY_VDF: &str = "~/.steam/registry.vdf";
+const REG_VDF_SOURCEMODS: &str = "Registry/HKCU/Software/Valve/Steam/SourceModInstallPath";
+
+/// How a steam root was found, useful to tell setups apart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SteamRootSource {
+    /// Set through `$STEAM_DIR`
+    Env,
+    /// Passed in manually, through `steam_root_from` or `SteamLocator::add_root`
+    Custom,
+    /// One of the symlinks steam creates, `~/.steam/steam` or `~/.steam/root`
+    Symlink,
+    /// A native install, `~/.local/share/Steam` or `~/.steam/debian-installation`
+    Native,
+    /// The Flatpak install
+    Flatpak,
+    /// The Snap package
+    Snap,
+    /// The path steam noted in `~/.steam/registry.vdf`
+    RegistryVdf
+}
 
-/// Returns the first steam root found.
-///
-/// The Result indicates if an invalid `STEAM_DIR` was set (if you set `no_tricks` you can disgard
-/// all Err, it will always return Ok). It return Ok on unset `STEAM_DIR`
-/// Err(Some(other)) indicates another standard steam root was found, Err(None) that none.
-/// Similarly, Ok(Some(root)) indicates the a root was found (this is the first one), Ok(None) indicates no root was found
+/// The built in locations where steam is commonly installed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SteamLocation {
+    /// `~/.steam/steam`, usually a symlink to the actual install
+    DotSteam,
+    /// `~/.steam/root`, usually a symlink to the actual install
+    DotSteamRoot,
+    /// `~/.local/share/Steam`
+    LocalShare,
+    /// `~/.steam/debian-installation`, used by the Debian/Ubuntu package
+    Debian,
+    /// `~/.var/app/com.valvesoftware.Steam/data/Steam/`
+    Flatpak,
+    /// `~/snap/steam/common/.local/share/Steam`
+    Snap,
+    /// The install noted in `~/.steam/registry.vdf`
+    RegistryVdf
+}
+
+impl SteamLocation {
+    /// The default search order
+    pub const DEFAULT_ORDER: [SteamLocation; 7] = [
+        SteamLocation::DotSteam,
+        SteamLocation::DotSteamRoot,
+        SteamLocation::LocalShare,
+        SteamLocation::Debian,
+        SteamLocation::Flatpak,
+        SteamLocation::Snap,
+        SteamLocation::RegistryVdf
+    ];
+
+    /// The path of this location, with the home folder resolved
+    ///
+    /// For `RegistryVdf` this reads the file, and returns None if it or the path within is missing
+    pub fn path(&self) -> Option<PathBuf> {
+        let path = match self {
+            SteamLocation::DotSteam => STEAM_DOT_STEAM,
+            SteamLocation::DotSteamRoot => STEAM_DOT_STEAM_ROOT,
+            SteamLocation::LocalShare => STEAM_LOCAL_SHARE,
+            SteamLocation::Debian => STEAM_DEBIAN,
+            SteamLocation::Flatpak => STEAM_FLATPAK,
+            SteamLocation::Snap => STEAM_SNAP,
+            SteamLocation::RegistryVdf => return steam_path_from_registry_vdf()
+        };
+
+        Some(expand_tilde(path).expect("A Path from home should always resolve"))
+    }
+
+    /// The source noted on roots found at this location
+    pub fn source(&self) -> SteamRootSource {
+        match self {
+            SteamLocation::DotSteam | SteamLocation::DotSteamRoot => SteamRootSource::Symlink,
+            SteamLocation::LocalShare | SteamLocation::Debian => SteamRootSource::Native,
+            SteamLocation::Flatpak => SteamRootSource::Flatpak,
+            SteamLocation::Snap => SteamRootSource::Snap,
+            SteamLocation::RegistryVdf => SteamRootSource::RegistryVdf
+        }
+    }
+}
+
+/// Steam notes the sourcemods folder within the registry.vdf, which is `<root>/steamapps/sourcemods`
+fn steam_path_from_registry_vdf() -> Option<PathBuf> {
+    let reg = parse_vdf_file(&expand_tilde(STEAM_REGISTRY_VDF)?)?;
+    let sourcemods = PathBuf::from(reg.get_path(REG_VDF_SOURCEMODS).ok()?.as_str().ok()?);
+
+    let steamapps = sourcemods.parent()?;
+    if !steamapps.file_name()?.eq_ignore_ascii_case("steamapps") {
+        return None;
+    }
+
+    steamapps.parent().map(Path::to_path_buf)
+}
+
+/// Builder to configure where (and in which order) steam roots are searched
 ///
-/// The order in which steam roots are found is:
+/// The default is the same search the free functions like `locate_all_steam_roots` do:
 /// - $STEAM_DIR (skipped if `no_tricks`)
 /// - ~/.steam/steam/
+/// - ~/.steam/root/
 /// - ~/.local/share/steam/
+/// - ~/.steam/debian-installation/
 /// - ~/.var/app/com.valvesoftware.Steam/data/Steam/
-pub fn find_steam_root() -> Result<Option<SteamRoot>, Option<SteamRoot>> {
-    let err = if cfg!(not(no_tricks)) {
-        match steam_root_env() {
-            Ok(root) => return Ok(Some(root)),
-            Err(err) => err
+/// - ~/snap/steam/common/.local/share/Steam/
+/// - the path noted in ~/.steam/registry.vdf
+///
+/// Custom roots are searched after `$STEAM_DIR`, but before the built in locations.
+/// ```
+/// use proton_finder::linux::{SteamLocator, SteamLocation};
+///
+/// let roots = SteamLocator::new()
+///     .use_env(false)
+///     .add_root("/mnt/games/Steam")
+///     .locations([SteamLocation::Flatpak, SteamLocation::LocalShare])
+///     .locate_all();
+/// ```
+#[derive(Debug, Clone)]
+pub struct SteamLocator {
+    env: bool,
+    custom: Vec<PathBuf>,
+    locations: Vec<SteamLocation>,
+    dedup: bool
+}
+
+impl Default for SteamLocator {
+    fn default() -> Self {
+        SteamLocator {
+            env: cfg!(not(feature = "no_tricks")),
+            custom: Vec::new(),
+            locations: SteamLocation::DEFAULT_ORDER.to_vec(),
+            dedup: true
         }
-    } else {
-        false
-    };
-
-    let path = expand_tilde(STEAM_DOT_STEAM).expect("A Path from home should always resolve");
-    if let Some(root) = steam_root_from(path) {
-        return match err {
-            true => Err(Some(root)),
-            false => Ok(Some(root))
-        };
     }
+}
 
-    // protontricks checks for both paths, so we will too
-    let path = expand_tilde(STEAM_LOCAL_SHARE).expect("A Path from home should always resolve");
-    if let Some(root) = steam_root_from(path) {
-        return match err {
-            true => Err(Some(root)),
-            false => Ok(Some(root))
-        };
+impl SteamLocator {
+    /// Creates a locator with the default search order
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// If `$STEAM_DIR` should be searched first.
+    ///
+    /// Defaults to true, or false with the `no_tricks` feature
+    pub fn use_env(mut self, enabled: bool) -> Self {
+        self.env = enabled;
+        self
+    }
+
+    /// Adds a path to search, custom roots are searched in the order they were added.
+    ///
+    /// If the path is not a steam root it is reported as a warning
+    pub fn add_root<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.custom.push(path.into());
+        self
+    }
+
+    /// Sets which of the built in locations are searched, and in which order.
+    ///
+    /// Locations that are not passed in are not searched, so an empty list only searches
+    /// `$STEAM_DIR` and the custom roots
+    pub fn locations<I: IntoIterator<Item = SteamLocation>>(mut self, locations: I) -> Self {
+        self.locations = locations.into_iter().collect();
+        self
+    }
+
+    /// Removes a built in location from the search
+    pub fn without(mut self, location: SteamLocation) -> Self {
+        self.locations.retain(|loc| *loc != location);
+        self
     }
 
-    // Flatpak
-    let path = expand_tilde(STEAM_FLATPAK).expect("A Path from home should always resolve");
-    if let Some(root) = steam_root_from(path) {
-        return match err {
-            true => Err(Some(root)),
-            false => Ok(Some(root))
+    /// If roots that are the same folder as an already found root are skipped (for example when
+    /// `~/.steam/steam` links to `~/.local/share/Steam`, or `$STEAM_DIR` is set to one of the
+    /// default locations). The first root found is kept, so the order stays the search order.
+    ///
+    /// Defaults to true
+    pub fn dedup(mut self, enabled: bool) -> Self {
+        self.dedup = enabled;
+        self
+    }
+
+    /// Returns the first steam root found.
+    ///
+    /// See `locate_all` for errors and warnings
+    pub fn locate(&self) -> SearchResult<SteamRoot> {
+        let SearchResult { result, warnings } = self.locate_all();
+        let result = result.and_then(|roots| roots.into_iter().next().ok_or(ProtonFinderError::NoSteamRoot));
+
+        SearchResult { result, warnings }
+    }
+
+    /// Returns all the steam roots found.
+    ///
+    /// An invalid `$STEAM_DIR` or custom root is reported as a warning, the search continues with
+    /// the other paths.
+    /// If no root was found the result is Err(NoSteamRoot).
+    pub fn locate_all(&self) -> SearchResult<Vec<SteamRoot>> {
+        // it will be rare we even get above 2, but still...
+        let mut roots = Vec::<SteamRoot>::with_capacity(4);
+        let mut warnings = Vec::new();
+        if self.env {
+            match steam_root_from_env() {
+                Ok(Some(root)) => roots.push(root),
+                Ok(None) => (),
+                Err(err) => warnings.push(err)
+            }
+        }
+
+        for path in self.custom.iter() {
+            match steam_root_from(path.clone()) {
+                Some(root) => self.push_root(&mut roots, root),
+                None => warnings.push(ProtonFinderError::InvalidRootPath(path.clone()))
+            }
+        }
+
+        for location in self.locations.iter() {
+            if let Some(root) = location.path().and_then(|path| steam_root_with_source(path, location.source())) {
+                self.push_root(&mut roots, root);
+            }
+        }
+
+        let result = match roots.is_empty() {
+            true => Err(ProtonFinderError::NoSteamRoot),
+            false => Ok(roots)
         };
+        SearchResult { result, warnings }
+    }
+
+    /// Returns the first prefix found for this game.
+    ///
+    /// The result is Err(NoSteamRoot) if steam was not found, and Err(PrefixNotFound) if no root
+    /// contains a prefix for the game.
+    pub fn locate_prefix(&self, game_id: u32) -> SearchResult<ProtonPrefix> {
+        let SearchResult { result, warnings } = self.locate_all();
+        let result = result.and_then(|roots| {
+            roots.iter().find_map(|root| root.get_prefix(game_id)).ok_or(ProtonFinderError::PrefixNotFound(game_id))
+        });
+
+        SearchResult { result, warnings }
     }
 
-    match err {
-        true => Err(None),
-        false => Ok(None)
+    /// Returns all prefixes found for this game.
+    ///
+    /// The result is Err(NoSteamRoot) if steam was not found, and Err(PrefixNotFound) if no root
+    /// contains a prefix for the game.
+    pub fn locate_all_prefixes(&self, game_id: u32) -> SearchResult<Vec<ProtonPrefix>> {
+        let SearchResult { result, warnings } = self.locate_all();
+        let result = result.and_then(|roots| {
+            // Different roots can share libraries, so we can come across the same prefix twice
+            // (unless dedup is disabled, then every prefix found is returned)
+            let mut ids = Vec::<FolderId>::new();
+            let prefixes: Vec<ProtonPrefix> = roots.iter()
+                .filter_map(|root| root.get_prefix(game_id))
+                .filter(|pfx| {
+                    if !self.dedup {
+                        return true;
+                    }
+
+                    let id = FolderId::of(&pfx.pfx);
+                    let new = !ids.contains(&id);
+                    ids.push(id);
+                    new
+                })
+                .collect();
+            match prefixes.is_empty() {
+                true => Err(ProtonFinderError::PrefixNotFound(game_id)),
+                false => Ok(prefixes)
+            }
+        });
+
+        SearchResult { result, warnings }
+    }
+
+    fn push_root(&self, roots: &mut Vec<SteamRoot>, root: SteamRoot) {
+        // Usually ~/.steam/steam links to ~/.local/share/steam , so if this is the case we will skip
+        // adding what is essentially the same folder twice
+        if self.dedup && roots.contains(&root) {
+            return;
+        }
+
+        roots.push(root);
     }
 }
 
-/// Returns all the steam roots found.
+/// Returns the first steam root found.
+///
+/// An invalid `$STEAM_DIR` is reported as a warning, the search continues with the other paths.
+/// If no root was found the result is Err(NoSteamRoot).
+///
+/// The order in which steam roots are found is:
+/// - $STEAM_DIR (skipped if `no_tricks`)
+/// - ~/.steam/steam/
+/// - ~/.steam/root/
+/// - ~/.local/share/steam/
+/// - ~/.steam/debian-installation/
+/// - ~/.var/app/com.valvesoftware.Steam/data/Steam/
+/// - ~/snap/steam/common/.local/share/Steam/
+/// - the path noted in ~/.steam/registry.vdf
+pub fn locate_steam_root() -> SearchResult<SteamRoot> {
+    SteamLocator::default().locate()
+}
+
+/// Returns the first steam root found.
 ///
 /// The Result indicates if an invalid `STEAM_DIR` was set (if you set `no_tricks` you can disgard
 /// all Err, it will always return Ok). It return Ok on unset `STEAM_DIR`
-/// If ~/.steam/steam symlinks to ~/.local/share/steam/ then the path will be included only once
+/// Err(Some(other)) indicates another standard steam root was found, Err(None) that none.
+/// Similarly, Ok(Some(root)) indicates the a root was found (this is the first one), Ok(None) indicates no root was found
 ///
 /// The order in which steam roots are found is:
 /// - $STEAM_DIR (skipped if `no_tricks`)
 /// - ~/.steam/steam/
+/// - ~/.steam/root/
 /// - ~/.local/share/steam/
+/// - ~/.steam/debian-installation/
 /// - ~/.var/app/com.valvesoftware.Steam/data/Steam/
-pub fn find_all_steam_roots() -> Result<Vec<SteamRoot>, Vec<SteamRoot>> {
-    // it will be rare we even get above 2, but still...
-    let mut roots = Vec::<SteamRoot>::with_capacity(4);
-    let err = if cfg!(not(no_tricks)) {
-        match steam_root_env() {
-            Ok(root) => {
-                roots.push(root);
-                false
-            },
-            Err(err) => err
-        }
-    } else {
-        false
-    };
-
-    // Technically, if the user passes in any of the three following paths as the $STEAM_DIR we
-    // will have that path twice... not a big deal, but still
-    
-    let path = expand_tilde(STEAM_DOT_STEAM).expect("A Path from home should always resolve");
-    if let Some(root) = steam_root_from(path.clone()) {
-        roots.push(root);
-    }
-
-    // Usually ~/.steam/steam links to ~/.local/share/steam , so if this is the case we will skip
-    // adding what is essentially the same folder twice
-    let local_path = expand_tilde(STEAM_LOCAL_SHARE).expect("A Path from home should always resolve");
-    let already = if path.is_symlink() {
-        if let (Ok(link), Ok(local_path)) = (path.canonicalize(), local_path.canonicalize()) {
-            local_path == link
-        } else {
-            false
-        }
-    } else {
-        false
-    };
-
-    if !already {
-        if let Some(root) = steam_root_from(local_path) {
-            roots.push(root);
-        }
-    }
+/// - ~/snap/steam/common/.local/share/Steam/
+/// - the path noted in ~/.steam/registry.vdf
+#[deprecated(since = "1.2.0", note = "use locate_steam_root, which reports errors as ProtonFinderError")]
+pub fn find_steam_root() -> Result<Option<SteamRoot>, Option<SteamRoot>> {
+    locate_steam_root().into_legacy()
+}
 
-    // Flatpak
-    let path = expand_tilde(STEAM_FLATPAK).expect("A Path from home should always resolve");
-    if let Some(root) = steam_root_from(path) {
-        roots.push(root);
-    }
+/// Returns all the steam roots found.
+///
+/// An invalid `$STEAM_DIR` is reported as a warning, the search continues with the other paths.
+/// If no root was found the result is Err(NoSteamRoot).
+/// Roots that are the same folder (like ~/.steam/steam symlinking to ~/.local/share/steam/) are
+/// included only once, at the position they were first found
+///
+/// The order in which steam roots are found is:
+/// - $STEAM_DIR (skipped if `no_tricks`)
+/// - ~/.steam/steam/
+/// - ~/.steam/root/
+/// - ~/.local/share/steam/
+/// - ~/.steam/debian-installation/
+/// - ~/.var/app/com.valvesoftware.Steam/data/Steam/
+/// - ~/snap/steam/common/.local/share/Steam/
+/// - the path noted in ~/.steam/registry.vdf
+///
+/// Use a `SteamLocator` to change the search order
+pub fn locate_all_steam_roots() -> SearchResult<Vec<SteamRoot>> {
+    SteamLocator::default().locate_all()
+}
 
-    match err {
-        true => Err(roots),
-        false => Ok(roots)
-    }
+/// Returns all the steam roots found.
+///
+/// The Result indicates if an invalid `STEAM_DIR` was set (if you set `no_tricks` you can disgard
+/// all Err, it will always return Ok). It return Ok on unset `STEAM_DIR`
+/// Roots that are the same folder (like ~/.steam/steam symlinking to ~/.local/share/steam/) are
+/// included only once, at the position they were first found
+///
+/// The order in which steam roots are found is:
+/// - $STEAM_DIR (skipped if `no_tricks`)
+/// - ~/.steam/steam/
+/// - ~/.steam/root/
+/// - ~/.local/share/steam/
+/// - ~/.steam/debian-installation/
+/// - ~/.var/app/com.valvesoftware.Steam/data/Steam/
+/// - ~/snap/steam/common/.local/share/Steam/
+/// - the path noted in ~/.steam/registry.vdf
+#[deprecated(since = "1.2.0", note = "use locate_all_steam_roots, which reports errors as ProtonFinderError")]
+pub fn find_all_steam_roots() -> Result<Vec<SteamRoot>, Vec<SteamRoot>> {
+    locate_all_steam_roots().into_legacy_vec()
 }
 
 // Name of the important paths
 const USER_REG: &str = "user.reg";
 const DOS_DEVICES: &str = "dosdevices";
-const REG_SHELL_FOLDERS: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\Shell Folders";
-const REG_VOLATILE: &str = "Volatile Environment";
+const REG_SHELL_FOLDERS: &str = "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\Shell Folders";
+const REG_USER_SHELL_FOLDERS: &str = "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\User Shell Folders";
+const REG_COMMON_SHELL_FOLDERS: &str = "HKLM\\Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\Shell Folders";
+const REG_COMMON_USER_SHELL_FOLDERS: &str = "HKLM\\Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\User Shell Folders";
+const REG_DRIVES: &str = "HKLM\\Software\\Wine\\Drives";
+const REG_PROFILE_LIST: &str = "HKLM\\Software\\Microsoft\\Windows NT\\CurrentVersion\\ProfileList";
 
 /// The Proton Prefix for a specfic game, containing the windows like enviroment in which save
 /// files and the like are stored
 #[derive(Debug, Clone)]
 pub struct ProtonPrefix {
     game: u32,
-    pfx: PathBuf
+    pfx: PathBuf,
+    create_missing: bool,
+    hives: Arc<HiveCache>
 }
 
 impl ProtonPrefix {
@@ -533,7 +1395,7 @@ impl ProtonPrefix {
             let mut user_reg = pfx.clone();
             user_reg.push(USER_REG);
             if user_reg.is_file() && dos_devices.is_dir() {
-                return Some(ProtonPrefix { game: 0, pfx });
+                return Some(ProtonPrefix { game: 0, pfx, create_missing: false, hives: Default::default() });
             }
         }
 
@@ -559,95 +1421,395 @@ impl ProtonPrefix {
     /// Returns the home folder for the user within the prefix,
     /// usually `C:\Users\username`
     pub fn home_dir(&self) -> Option<PathBuf> {
-        self.get_path_from_registry(REG_VOLATILE, "USERPROFILE")
+        self.known_folder(KnownFolder::Profile)
     }
 
     /// Returns the AppData\Roaming folder within the Home folder within the prefix
     pub fn appdata_roaming(&self) -> Option<PathBuf> {
-        self.get_path_from_registry(REG_SHELL_FOLDERS, "AppData")
+        self.known_folder(KnownFolder::RoamingAppData)
     }
 
     /// Returns the AppData\Local folder within the Home folder within the prefix
     pub fn appdata_local(&self) -> Option<PathBuf> {
-        self.get_path_from_registry(REG_SHELL_FOLDERS, "Local AppData")
+        self.known_folder(KnownFolder::LocalAppData)
     }
 
     /// Returns the AppData\LocalLow folder within the Home folder within the prefix
     pub fn appdata_local_low(&self) -> Option<PathBuf> {
-        // Yeah, this is the key of hell... but if it works...
-        self.get_path_from_registry(REG_SHELL_FOLDERS, "{A520A1A4-1780-4FF6-BD18-167343C5AF16}")
+        self.known_folder(KnownFolder::LocalAppDataLow)
     }
 
     /// Returns the Music folder within the Home folder within the prefix
     pub fn music_dir(&self) -> Option<PathBuf> {
-        self.get_path_from_registry(REG_SHELL_FOLDERS, "My Music")
+        self.known_folder(KnownFolder::Music)
     }
 
     /// Returns the Videos folder within the Home folder within the prefix
     pub fn videos_dir(&self) -> Option<PathBuf> {
-        self.get_path_from_registry(REG_SHELL_FOLDERS, "My Videos")
+        self.known_folder(KnownFolder::Videos)
     }
 
     /// Returns the Pictures folder within the Home folder within the prefix
     pub fn picture_dir(&self) -> Option<PathBuf> {
-        self.get_path_from_registry(REG_SHELL_FOLDERS, "My Pictures")
+        self.known_folder(KnownFolder::Pictures)
     }
 
     /// Returns the Documents folder within the Home folder within the prefix
     pub fn documents_dir(&self) -> Option<PathBuf> {
-        self.get_path_from_registry(REG_SHELL_FOLDERS, "Personal")
+        self.known_folder(KnownFolder::Documents)
     }
 
     /// Returns the Downloads folder within the Home folder within the prefix
     pub fn downloads_dir(&self) -> Option<PathBuf> {
-        self.get_path_from_registry(REG_SHELL_FOLDERS, "{374DE290-123F-4565-9164-39C4925E467B}")
+        self.known_folder(KnownFolder::Downloads)
     }
 
     /// Returns the Desktop folder within the Home folder within the prefix
     pub fn desktop_dir(&self) -> Option<PathBuf> {
-        self.get_path_from_registry(REG_SHELL_FOLDERS, "Desktop")
+        self.known_folder(KnownFolder::Desktop)
+    }
+
+    /// Returns the path of a known folder within the prefix (like `KnownFolder::SavedGames`).
+    ///
+    /// The first of these that exists is returned:
+    /// - the `Shell Folders` in the registry (by the legacy name or the guid)
+    /// - the `User Shell Folders` in the registry
+    /// - the default location of the folder on windows, with the variables from the registry
+    ///   (variables missing there get wine's defaults, like `C:\users\steamuser`)
+    ///
+    /// Locations with variables that can't be resolved are skipped.
+    /// If none exists None is returned, except with `create_missing` set, then the first is created.
+    /// Virtual folders always return None
+    pub fn known_folder(&self, folder: KnownFolder) -> Option<PathBuf> {
+        let reg = self.registry();
+        let expand = |path: &str| reg.try_expand_env_or(path, wine_default_env);
+
+        let mut candidates = Vec::new();
+        for key in [REG_SHELL_FOLDERS, REG_USER_SHELL_FOLDERS] {
+            candidates.extend([folder.user_shell_folder(), Some(folder.guid())].into_iter().flatten()
+                .filter_map(|name| expand(reg.get_value(key, name)?.as_str()?)));
+        }
+        for key in [REG_COMMON_SHELL_FOLDERS, REG_COMMON_USER_SHELL_FOLDERS] {
+            candidates.extend(folder.common_shell_folder()
+                .and_then(|name| expand(reg.get_value(key, name)?.as_str()?)));
+        }
+
+        let default = match folder {
+            KnownFolder::SystemX86 if reg.is_win64() => Some("%windir%\\SysWOW64"),
+            KnownFolder::SystemX86 => Some("%windir%\\system32"),
+            folder => folder.default_path()
+        };
+        candidates.extend(default.and_then(expand));
+
+        self.find_folder(&candidates)
+    }
+
+    /// Returns the temp folder within the prefix (`%TEMP%`), same as `known_folder`
+    /// this falls back to wine's default
+    pub fn temp_dir(&self) -> Option<PathBuf> {
+        self.find_folder(self.registry().try_expand_env_or("%TEMP%", wine_default_env).as_slice())
+    }
+
+    /// When set, the folder getters (like `documents_dir` or `known_folder`) create the folder
+    /// if it does not exist yet, so a first time config can be written into it.
+    ///
+    /// Per default missing folders return None
+    pub fn create_missing(mut self, enabled: bool) -> Self {
+        self.create_missing = enabled;
+        self
+    }
+
+    /// Returns the first of the windows paths that exists, or creates the first one if `create_missing` is set.
+    /// The paths have to be expanded already
+    fn find_folder(&self, candidates: &[String]) -> Option<PathBuf> {
+        if let Some(path) = candidates.iter().find_map(|path| self.walk_windows_path(path, false).ok()) {
+            return path.canonicalize().ok();
+        }
+
+        if self.create_missing {
+            let path = candidates.first()?;
+            return self.walk_windows_path(path, true).ok()?.canonicalize().ok();
+        }
+
+        None
     }
 
     fn get_path_from_registry(&self, key: &str, sub_key: &str) -> Option<PathBuf> {
-        let mut user_reg = self.pfx.clone();
-        user_reg.push(USER_REG);
-        if let Some(user_reg) = RegParser::new(user_reg) {
-            if let Some(val) = user_reg.open_key(key) {
-                if let Some(path) = val.get(sub_key) {
-                    return self.parse_windows_path(path).canonicalize().ok();
+        let path = self.registry().get_value(key, sub_key)?.as_str()?.to_string();
+        self.parse_windows_path(&path).canonicalize().ok()
+    }
+
+    /// Returns the registry of this prefix, accessed through paths like `HKCU\Software\Wine`
+    ///
+    /// The registry files are read on first access, and the parsed files are shared by all
+    /// registries of this prefix (and it's clones). A file is only read again once it changed,
+    /// like when wine wrote it on exit.
+    pub fn registry(&self) -> PrefixRegistry {
+        PrefixRegistry::new(self.pfx.clone(), self.hives.clone())
+    }
+
+    /// If the wineserver of this prefix is running (so the game or another program in the prefix
+    /// is running).
+    ///
+    /// While it runs wine keeps the registry in memory, and overwrites the .reg files on exit
+    pub fn is_wineserver_running(&self) -> bool {
+        registry::wineserver_running(&self.pfx)
+    }
+
+    /// Turns a windows path into the path within this prefix the same way wine does, by matching
+    /// every folder and the file case insensitive against what exists on disk
+    /// (so `Documents\My Games\Config.ini` finds `documents/My Games/config.ini`).
+    ///
+    /// Environment variables are expanded first.
+    /// If the path does not exist Err contains the closest existing ancestor, so you can create
+    /// the missing folders from there
+    pub fn resolve_windows_path(&self, str: &str) -> Result<PathBuf, PathBuf> {
+        self.walk_windows_path(&self.expand_env(str), false)
+    }
+
+    /// Walks the windows path the same way as `resolve_windows_path`, creating the missing folders if `create` is set.
+    /// The path has to be expanded already, so a `%` within a value is not expanded a second time
+    fn walk_windows_path(&self, str: &str, create: bool) -> Result<PathBuf, PathBuf> {
+        let mut parts = str.split(['\\', '/']).filter(|part| !part.is_empty());
+
+        let mut path = self.get_pfx_path();
+        let Some(drive) = parts.next() else {
+            return Ok(path);
+        };
+
+        path.push(DOS_DEVICES);
+        path = find_entry_ignore_case(&path, drive).ok_or(path)?;
+
+        for part in parts {
+            match part {
+                "." => (),
+                ".." => {
+                    // Going above the drive root stays at the root, same as windows
+                    if path.parent().and_then(Path::file_name) != Some(std::ffi::OsStr::new(DOS_DEVICES)) {
+                        path.pop();
+                    }
+                },
+                part => {
+                    let part: String = part.chars().map(from_wine_char).collect();
+                    path = match find_entry_ignore_case(&path, &part) {
+                        Some(found) => found,
+                        None if create && fs::create_dir(path.join(&part)).is_ok() => path.join(&part),
+                        None => return Err(path)
+                    }
                 }
             }
         }
 
-        None
+        Ok(path)
     }
 
-    /// Returns the public user folder within the prefix
-    pub fn public_user_dir(&self) -> Option<PathBuf> {
-        let mut user_reg = self.pfx.clone();
-        user_reg.push("system.reg");
-        if let Some(user_reg) = RegParser::new(user_reg) {
-            if let Some(val) = user_reg.open_key("Software\\Microsoft\\Windows NT\\CurrentVersion\\ProfileList") {
-                if let Some(path) = val.get("Public") {
-                    return self.parse_windows_path(path).canonicalize().ok();
+    /// Turns a path on this system into the windows path the programs within the prefix see
+    /// (like `C:\users\steamuser\Documents`), the inverse of `parse_windows_path`.
+    ///
+    /// The drive whose target contains the path the most specific is used, so a path within the
+    /// prefix is on `C:` instead of `Z:` (which usually maps all of `/`).
+    /// Characters that are not allowed in windows names (`\ : * ? " < > |`) are mapped the same
+    /// way wine does, to U+F000 plus the character.
+    ///
+    /// Returns None if no drive contains the path, or a name within it is not valid UTF-8
+    /// (these have no windows name we could reliably reproduce)
+    pub fn to_windows_path(&self, path: &Path) -> Option<String> {
+        let path = canonicalize_existing(path)?;
+
+        let (letter, rest) = self.dos_devices().into_iter()
+            .filter_map(|(letter, target)| Some((letter, path.strip_prefix(target).ok()?.to_path_buf())))
+            .min_by_key(|(_, rest)| rest.components().count())?;
+
+        let mut res = format!("{}:", letter.to_ascii_uppercase());
+        for comp in rest.components() {
+            res.push('\\');
+            res.extend(comp.as_os_str().to_str()?.chars().map(to_wine_char));
+        }
+        if res.len() == 2 {
+            res.push('\\');
+        }
+
+        Some(res)
+    }
+
+    /// Returns the drives in the dosdevices folder, with their resolved target
+    fn dos_devices(&self) -> Vec<(char, PathBuf)> {
+        self.drives().into_iter().filter_map(|drive| Some((drive.letter, drive.target?))).collect()
+    }
+
+    /// Returns the drives of this prefix (sorted by letter), as set in the dosdevices folder.
+    ///
+    /// The type is read from `HKLM\Software\Wine\Drives`, drives not noted there are either
+    /// the drive within the prefix (`C:`), the root of the system (usually `Z:`), or a fixed drive
+    pub fn drives(&self) -> Vec<Drive> {
+        let mut devices = self.pfx.clone();
+        devices.push(DOS_DEVICES);
+        let types = self.registry().values(REG_DRIVES).map(<[_]>::to_vec).unwrap_or_default();
+        let drive_c = self.pfx.join("drive_c").canonicalize().ok();
+
+        let mut res = Vec::new();
+        if let Ok(iter) = devices.read_dir() {
+            for item in iter.flatten() {
+                let name = item.file_name();
+                let Some(name) = name.to_str() else {
+                    continue;
+                };
+
+                // Drives are named "c:", there are also devices like "com1" and "c::" (the raw device)
+                let mut chars = name.chars();
+                let (Some(letter), Some(':'), None) = (chars.next(), chars.next(), chars.next()) else {
+                    continue;
+                };
+                if !letter.is_ascii_alphabetic() {
+                    continue;
                 }
+
+                let letter = letter.to_ascii_lowercase();
+                let target = item.path().canonicalize().ok();
+                let reg_type = types.iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(&format!("{}:", letter)))
+                    .and_then(|(_, val)| DriveType::from_reg_name(val.as_str()?));
+
+                let kind = match reg_type {
+                    Some(kind) if kind != DriveType::Fixed => kind,
+                    _ if target.is_some() && target == drive_c => DriveType::Prefix,
+                    _ if target.as_deref() == Some(Path::new("/")) => DriveType::Root,
+                    _ => DriveType::Fixed
+                };
+
+                res.push(Drive { letter, path: item.path(), target, kind });
             }
         }
 
-        None
+        res.sort_by_key(|drive| drive.letter);
+        res
+    }
+
+    /// Adds a drive that points to the target folder (for example to mount a mod folder).
+    ///
+    /// The type is noted in the registry, with None (or `Fixed`) no type is set.
+    /// Fails if the drive letter is already in use, the target is not a folder, the type is
+    /// `Prefix` or `Root` (these are only reported by `drives`), or the wineserver of the prefix
+    /// is running (wine only reads the drives on start)
+    pub fn add_drive(&self, letter: char, target: &Path, kind: Option<DriveType>) -> Result<Drive, ProtonFinderError> {
+        let letter = Self::check_drive_letter(letter)?;
+        if matches!(kind, Some(DriveType::Prefix | DriveType::Root)) {
+            return Err(ProtonFinderError::InvalidDriveTarget(target.to_path_buf()));
+        }
+        if self.is_wineserver_running() {
+            return Err(ProtonFinderError::WineserverRunning(self.get_pfx_path()));
+        }
+
+        let target = target.canonicalize()?;
+        if !target.is_dir() {
+            return Err(ProtonFinderError::InvalidDriveTarget(target));
+        }
+
+        let mut path = self.pfx.clone();
+        path.push(DOS_DEVICES);
+        path.push(format!("{}:", letter));
+        if path.symlink_metadata().is_ok() {
+            return Err(ProtonFinderError::DriveInUse(letter));
+        }
+
+        // The link is created first, so a failed link leaves the registry untouched
+        std::os::unix::fs::symlink(&target, &path)?;
+
+        if let Some(name) = kind.and_then(|kind| kind.reg_name()).filter(|name| *name != "hd") {
+            let value_name = format!("{}:", letter);
+            let mut reg = self.registry();
+            let res = match reg.set_value(REG_DRIVES, &value_name, RegValue::Str(name.to_string())) {
+                true => reg.save(),
+                false => Err(ProtonFinderError::RegistryKeyMissing(REG_DRIVES.to_string()))
+            };
+
+            // Without the type wine would treat it as a fixed drive, so we roll back the whole drive
+            if let Err(err) = res {
+                reg.delete_value(REG_DRIVES, &value_name);
+                let _ = fs::remove_file(&path);
+                return Err(err);
+            }
+        }
+
+        Ok(Drive { letter, path, target: Some(target), kind: kind.unwrap_or(DriveType::Fixed) })
+    }
+
+    /// Removes a drive (only the link in the dosdevices folder, the target is not touched) and
+    /// it's type in the registry.
+    ///
+    /// Refuses to remove `C:`, and fails if the wineserver of the prefix is running
+    pub fn remove_drive(&self, letter: char) -> Result<(), ProtonFinderError> {
+        let letter = Self::check_drive_letter(letter)?;
+        if letter == 'c' {
+            return Err(ProtonFinderError::DriveNotRemovable(letter));
+        }
+        if self.is_wineserver_running() {
+            return Err(ProtonFinderError::WineserverRunning(self.get_pfx_path()));
+        }
+
+        let mut path = self.pfx.clone();
+        path.push(DOS_DEVICES);
+        path.push(format!("{}:", letter));
+        if !path.symlink_metadata()?.is_symlink() {
+            return Err(ProtonFinderError::DriveNotRemovable(letter));
+        }
+
+        // Same as in add_drive the link goes first, and is restored if the registry can't be saved
+        let target = fs::read_link(&path)?;
+        fs::remove_file(&path)?;
+
+        let mut reg = self.registry();
+        if reg.delete_value(REG_DRIVES, &format!("{}:", letter)).is_some() {
+            if let Err(err) = reg.save() {
+                let _ = std::os::unix::fs::symlink(&target, &path);
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_drive_letter(letter: char) -> Result<char, ProtonFinderError> {
+        match letter.is_ascii_alphabetic() {
+            true => Ok(letter.to_ascii_lowercase()),
+            false => Err(ProtonFinderError::InvalidDriveLetter(letter))
+        }
+    }
+
+    /// Returns the public user folder within the prefix
+    pub fn public_user_dir(&self) -> Option<PathBuf> {
+        self.get_path_from_registry(REG_PROFILE_LIST, "Public")
+            .or_else(|| self.known_folder(KnownFolder::Public))
     }
 
 
+    /// Replaces all `%VARIABLE%` (like `%USERPROFILE%` or `%APPDATA%`) with their values within
+    /// this prefix, unknown variables are kept as is.
+    ///
+    /// See `PrefixRegistry::env_var` for where the variables are read from
+    pub fn expand_env(&self, str: &str) -> String {
+        if !str.contains('%') {
+            // Saves us from reading the registry
+            return str.to_string();
+        }
+
+        self.registry().expand_env(str)
+    }
+
     /// Turns a string with a absolute windows formated path
     /// into the complete path within this prefix
+    ///
+    /// Environment variables (like `%APPDATA%\Game`) are expanded first
     pub fn parse_windows_path(&self, str: &str) -> PathBuf {
+        let str = &self.expand_env(str);
         if str.is_empty() {
             return self.get_pfx_path();
         }
 
         let res = str.replace("\\\\", "/"); // paths in reg are written with two \\
-        let mut res = res.replace("\\", "/"); // but if someone needs a regualr path converted, this deals with it
-        
+        let res = res.replace("\\", "/"); // but if someone needs a regualr path converted, this deals with it
+        let mut res: String = res.chars().map(from_wine_char).collect();
+
         let (letter,_) = res.split_at_mut(1);
         letter.make_ascii_lowercase(); // the dirve names in the prefix are lowercase
 
@@ -659,6 +1821,26 @@ impl ProtonPrefix {
     }
 }
 
+/// Returns the first prefix found for this game.
+///
+/// There is a chance there are multiple prefixes through multiple steam installs.
+/// An invalid `$STEAM_DIR` is reported as a warning, the search continues with the other paths.
+/// The result is Err(NoSteamRoot) if steam was not found, and Err(PrefixNotFound) if no root
+/// contains a prefix for the game.
+///
+/// The search order is:
+/// - $STEAM_DIR (skipped if `no_tricks`)
+/// - ~/.steam/steam/
+/// - ~/.steam/root/
+/// - ~/.local/share/steam/
+/// - ~/.steam/debian-installation/
+/// - ~/.var/app/com.valvesoftware.Steam/data/Steam/
+/// - ~/snap/steam/common/.local/share/Steam/
+/// - the path noted in ~/.steam/registry.vdf
+pub fn locate_prefix(game_id: u32) -> SearchResult<ProtonPrefix> {
+    SteamLocator::default().locate_prefix(game_id)
+}
+
 /// Returns the first prefix found for this game.
 ///
 /// There is a chance there are multiple prefixes through multiple steam installs.
@@ -670,27 +1852,35 @@ impl ProtonPrefix {
 /// The search order is:
 /// - $STEAM_DIR (skipped if `no_tricks`)
 /// - ~/.steam/steam/
+/// - ~/.steam/root/
 /// - ~/.local/share/steam/
+/// - ~/.steam/debian-installation/
 /// - ~/.var/app/com.valvesoftware.Steam/data/Steam/
+/// - ~/snap/steam/common/.local/share/Steam/
+/// - the path noted in ~/.steam/registry.vdf
+#[deprecated(since = "1.2.0", note = "use locate_prefix, which reports errors as ProtonFinderError")]
 pub fn find_prefix(game_id: u32) -> Result<Option<ProtonPrefix>, Option<ProtonPrefix>> {
-    let (roots, err) = match find_all_steam_roots() {
-        Err(res) => (res, true),
-        Ok(res) => (res, false)
-    };
-
-    for root in roots {
-        if let Some(pref) = root.get_prefix(game_id) {
-            return match err {
-                true => Err(Some(pref)),
-                false => Ok(Some(pref))
-            };
-        }
-    }
+    locate_prefix(game_id).into_legacy()
+}
 
-    match err {
-        true => Err(None),
-        false => Ok(None)
-    }
+/// Returns all prefixes found for this game.
+///
+/// There is a chance there are multiple prefixes through multiple steam installs.
+/// An invalid `$STEAM_DIR` is reported as a warning, the search continues with the other paths.
+/// The result is Err(NoSteamRoot) if steam was not found, and Err(PrefixNotFound) if no root
+/// contains a prefix for the game.
+///
+/// The search order is:
+/// - $STEAM_DIR (skipped if `no_tricks`)
+/// - ~/.steam/steam/
+/// - ~/.steam/root/
+/// - ~/.local/share/steam/
+/// - ~/.steam/debian-installation/
+/// - ~/.var/app/com.valvesoftware.Steam/data/Steam/
+/// - ~/snap/steam/common/.local/share/Steam/
+/// - the path noted in ~/.steam/registry.vdf
+pub fn locate_all_prefixes(game_id: u32) -> SearchResult<Vec<ProtonPrefix>> {
+    SteamLocator::default().locate_all_prefixes(game_id)
 }
 
 /// Returns all prefixes found for this game.
@@ -702,121 +1892,182 @@ pub fn find_prefix(game_id: u32) -> Result<Option<ProtonPrefix>, Option<ProtonPr
 /// The search order is:
 /// - $STEAM_DIR (skipped if `no_tricks`)
 /// - ~/.steam/steam/
+/// - ~/.steam/root/
 /// - ~/.local/share/steam/
+/// - ~/.steam/debian-installation/
 /// - ~/.var/app/com.valvesoftware.Steam/data/Steam/
+/// - ~/snap/steam/common/.local/share/Steam/
+/// - the path noted in ~/.steam/registry.vdf
+#[deprecated(since = "1.2.0", note = "use locate_all_prefixes, which reports errors as ProtonFinderError")]
 pub fn find_all_prefixes(game_id: u32) -> Result<Vec<ProtonPrefix>, Vec<ProtonPrefix>> {
-    let mut prefixes = Vec::<ProtonPrefix>::with_capacity(4);
-    let (roots, err) = match find_all_steam_roots() {
-        Err(res) => (res, true),
-        Ok(res) => (res, false)
-    };
+    locate_all_prefixes(game_id).into_legacy_vec()
+}
+
+/// A drive within a prefix
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Drive {
+    /// The drive letter, in lowercase
+    pub letter: char,
+    /// The link within the dosdevices folder
+    pub path: PathBuf,
+    /// Where the drive points to, None if the target does not exist
+    pub target: Option<PathBuf>,
+    pub kind: DriveType
+}
+
+/// The type of a drive within a prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DriveType {
+    /// The drive within the prefix (`drive_c`)
+    Prefix,
+    /// The root of the system (`/`), usually `Z:`
+    Root,
+    /// A regular hard drive
+    Fixed,
+    /// A floppy or other removable drive
+    Removable,
+    CdRom,
+    Network,
+    RamDisk
+}
 
-    for root in roots {
-        if let Some(pref) = root.get_prefix(game_id) {
-            prefixes.push(pref);
+impl DriveType {
+    /// The name wine uses for the type in `HKLM\Software\Wine\Drives`
+    pub fn reg_name(&self) -> Option<&'static str> {
+        match self {
+            DriveType::Prefix | DriveType::Root => None,
+            DriveType::Fixed => Some("hd"),
+            DriveType::Removable => Some("floppy"),
+            DriveType::CdRom => Some("cdrom"),
+            DriveType::Network => Some("network"),
+            DriveType::RamDisk => Some("ramdisk")
         }
     }
 
-    match err {
-        true => Err(prefixes),
-        false => Ok(prefixes)
+    fn from_reg_name(name: &str) -> Option<DriveType> {
+        match name.to_ascii_lowercase().as_str() {
+            "hd" => Some(DriveType::Fixed),
+            "floppy" => Some(DriveType::Removable),
+            "cdrom" => Some(DriveType::CdRom),
+            "network" => Some(DriveType::Network),
+            "ramdisk" => Some(DriveType::RamDisk),
+            _ => None
+        }
     }
 }
 
-/// Acts as a wrapper for reading registry entries
-#[derive(Debug)]
-pub struct RegParser {
-    reg: File
+/// The variables as wine sets them up in a new prefix (proton always names the user steamuser)
+const WINE_DEFAULT_ENV: [(&str, &str); 18] = [
+    ("USERPROFILE", "C:\\users\\steamuser"),
+    ("APPDATA", "C:\\users\\steamuser\\AppData\\Roaming"),
+    ("LOCALAPPDATA", "C:\\users\\steamuser\\AppData\\Local"),
+    ("TEMP", "C:\\users\\steamuser\\AppData\\Local\\Temp"),
+    ("TMP", "C:\\users\\steamuser\\AppData\\Local\\Temp"),
+    ("PUBLIC", "C:\\users\\Public"),
+    ("ALLUSERSPROFILE", "C:\\ProgramData"),
+    ("ProgramData", "C:\\ProgramData"),
+    ("SystemDrive", "C:"),
+    ("SystemRoot", "C:\\windows"),
+    ("windir", "C:\\windows"),
+    ("ProgramFiles", "C:\\Program Files"),
+    ("ProgramFiles(x86)", "C:\\Program Files (x86)"),
+    ("ProgramW6432", "C:\\Program Files"),
+    ("CommonProgramFiles", "C:\\Program Files\\Common Files"),
+    ("CommonProgramFiles(x86)", "C:\\Program Files (x86)\\Common Files"),
+    ("CommonProgramW6432", "C:\\Program Files\\Common Files"),
+    ("HOMEDRIVE", "C:")
+];
+
+/// Looks up a variable in wine's defaults, as fallback for the ones missing in the registry
+fn wine_default_env(name: &str) -> Option<String> {
+    WINE_DEFAULT_ENV.iter().find(|(var, _)| var.eq_ignore_ascii_case(name)).map(|(_, value)| value.to_string())
 }
 
-impl RegParser {
-    /// Creates a wrapper around a .reg Registry file
-    pub fn new(reg_file: PathBuf) -> Option<RegParser> {
-        if let Ok(file) = File::open(reg_file) {
-            Some(RegParser { reg: file })
-        } else {
-            None
-        }
-    }
-    
-    /// Tries to open a given key.
-    /// This key has to be formated in a windows path format
-    pub fn open_key(&self, key_path: &str) -> Option<HashMap<String, String>> {
+/// Characters that are valid in linux names, but not in windows names
+const WINE_MAPPED_CHARS: [char; 8] = ['\\', ':', '*', '?', '"', '<', '>', '|'];
 
-        // Serves to read the section header, or determine if there is one at all
-        fn read_line_section<'a>(trimed: &'a str) -> Option<&'a str> {
-            if let Some(text) = trimed.strip_prefix('[') {
-                // Likely found a section header
-                if let Some((path,_)) = text.split_once(']') {
-                    // split_once insures the character exists, and it doesn't matter if there is a
-                    // postfix or not, we are anyway not interested in it
+/// Maps a character of a linux name to the one wine shows to the programs in the prefix
+fn to_wine_char(c: char) -> char {
+    match WINE_MAPPED_CHARS.contains(&c) {
+        true => char::from_u32(0xf000 + c as u32).unwrap_or(c),
+        false => c
+    }
+}
 
-                    return Some(path);
-                }
-                
-            }
+/// The inverse of `to_wine_char`
+fn from_wine_char(c: char) -> char {
+    match char::from_u32((c as u32).wrapping_sub(0xf000)) {
+        Some(mapped) if WINE_MAPPED_CHARS.contains(&mapped) => mapped,
+        _ => c
+    }
+}
 
-            None
+/// Canonicalizes the path, even if the path does not exist (yet), by canonicalizing the closest
+/// existing ancestor
+fn canonicalize_existing(path: &Path) -> Option<PathBuf> {
+    let path = std::path::absolute(path).ok()?;
+
+    let mut missing = Vec::new();
+    let mut current = path.as_path();
+    loop {
+        if let Ok(mut res) = current.canonicalize() {
+            res.extend(missing.iter().rev());
+            return Some(res);
         }
 
-        // Serves to read the sub key and write it into the map
-        fn read_sub_key(trimed: &str, map: &mut HashMap<String, String>) -> bool {
-            if let Some(part) = trimed.strip_prefix('"') {
-                if let Some((key, part)) = part.split_once('"') {
-
-                    // This will still not capture dwords, but whatever
-                    if let Some(val_part) = part.strip_prefix('=') {
-                        if let Some((_,val_untrimmed)) = val_part.split_once('"') {
-                            if let Some((val,_)) = val_untrimmed.split_once('"') {
-                                map.insert(key.to_string(), val.to_string());
-                                return true;
-                            }
-                        }
-                    }
-
-                    // In case we didn't find any other value we insert the valid key
-                    map.insert(key.to_string(), String::new());
-                    return true;
-                }
-            }
+        missing.push(current.file_name()?);
+        current = current.parent()?;
+    }
+}
 
-            false
-        }
+/// Acts as a wrapper for reading registry entries
+///
+/// The file is read once into a `Registry` when opened
+#[derive(Debug)]
+pub struct RegParser {
+    reg: Registry
+}
 
-        // The reg files format paths with \\, in programming \\ becomes one backslash, so \\ is \\\\
-        // But since someone could pass a corrected or a standard windows style path, we normalize
-        // We do two replace to not accidentally produce  backslashes
-        let key_path = key_path.replace("\\\\", "\\").replace("\\", "\\\\");
+impl RegParser {
+    /// Creates a wrapper around a .reg Registry file
+    pub fn new(reg_file: PathBuf) -> Option<RegParser> {
+        Self::open(reg_file).ok()
+    }
 
-        let mut reader = BufReader::new(self.reg.try_clone().ok()?);
-        let mut output = None;
+    /// Creates a wrapper around a .reg Registry file, reporting why the file could not be opened
+    pub fn open(reg_file: PathBuf) -> Result<RegParser, ProtonFinderError> {
+        Ok(RegParser { reg: Registry::open(&reg_file)? })
+    }
 
-        let mut line = String::new();
-        while let Ok(length) = reader.read_line(&mut line) {
-            // read_line does not Err on EOF, it will instead return Ok(0), which we catch
-            if length == 0 {
-                break;
-            }
+    /// The loaded registry, to enumerate keys and read the metadata
+    pub fn registry(&self) -> &Registry {
+        &self.reg
+    }
 
-            let trimed = line.trim();
-            
-            if let Some(path) = read_line_section(trimed) {
-                if output.is_none() && path == key_path {
-                    // We found our key
-                    output = Some(HashMap::new());
-                } else if output.is_some() {
-                    break;
-                }
-                
-            }
+    /// Opens a given key, same as `open_key`, but reports a missing key as Err(RegistryKeyMissing)
+    pub fn try_open_key(&self, key_path: &str) -> Result<HashMap<String, String>, ProtonFinderError> {
+        self.open_key(key_path).ok_or_else(|| ProtonFinderError::RegistryKeyMissing(key_path.to_string()))
+    }
 
-            if let Some(map) = output.as_mut() {
-                read_sub_key(trimed, map);
-            }
-            
-            line.clear();
-        }
+    /// Tries to open a given key.
+    /// This key has to be formated in a windows path format
+    ///
+    /// Only the text of string values is returned (with escape sequences resolved), all other
+    /// types are included with an empty string. Use `open_key_values` to get the typed values.
+    pub fn open_key(&self, key_path: &str) -> Option<HashMap<String, String>> {
+        let values = self.reg.values(key_path)?;
+        Some(values.iter().map(|(name, val)| {
+            let text = val.as_str().unwrap_or_default().to_string();
+            (name.clone(), text)
+        }).collect())
+    }
 
-        output
+    /// Tries to open a given key, returning the typed values.
+    /// This key has to be formated in a windows path format
+    ///
+    /// The default value (`@`) is returned with an empty name.
+    /// Values that can't be parsed are skipped, the order is the one wine keeps (sorted by name).
+    pub fn open_key_values(&self, key_path: &str) -> Option<Vec<(String, RegValue)>> {
+        self.reg.values(key_path).map(<[_]>::to_vec)
     }
 }
@@ -0,0 +1,140 @@
+use std::{collections::BTreeMap, fmt};
+
+use serde::{de::{self, IgnoredAny, MapAccess, Visitor}, Deserialize, Deserializer, Serialize};
+
+use super::InstalledDepot;
+
+/// The content of `steamapps/libraryfolders.vdf`
+///
+/// Load it with `linux::vdf::from_file`, the libraries are keyed by their index
+///
+/// Older steam versions wrote `LibraryFolders` with only the path per index (`"1" "/path"`) and
+/// some stats keys (`ContentStatsID`, `TimeNextStatsReport`), those files are read as well:
+/// The stats keys are skipped and the libraries only have their path set.
+/// Writing always produces the current layout.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LibraryFoldersVdf {
+    #[serde(rename = "libraryfolders", alias = "LibraryFolders", deserialize_with = "deserialize_libraries")]
+    pub libraries: BTreeMap<u32, LibraryFolder>
+}
+
+/// Reads the libraries of both layouts, skipping all keys that are not an index
+fn deserialize_libraries<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BTreeMap<u32, LibraryFolder>, D::Error> {
+    struct LibrariesVisitor;
+
+    impl<'de> Visitor<'de> for LibrariesVisitor {
+        type Value = BTreeMap<u32, LibraryFolder>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a struct of library folders")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut libraries = BTreeMap::new();
+            while let Some(key) = map.next_key::<String>()? {
+                match key.parse::<u32>() {
+                    Ok(index) => { libraries.insert(index, map.next_value::<LibraryEntry>()?.0); },
+                    Err(_) => { map.next_value::<IgnoredAny>()?; }
+                }
+            }
+            Ok(libraries)
+        }
+    }
+
+    deserializer.deserialize_map(LibrariesVisitor)
+}
+
+/// A library that is either the full struct, or just the path (old layout)
+struct LibraryEntry(LibraryFolder);
+
+impl<'de> Deserialize<'de> for LibraryEntry {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EntryVisitor;
+
+        impl<'de> Visitor<'de> for EntryVisitor {
+            type Value = LibraryEntry;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a library folder or a path")
+            }
+
+            fn visit_str<E: de::Error>(self, path: &str) -> Result<Self::Value, E> {
+                self.visit_string(path.to_string())
+            }
+
+            fn visit_string<E: de::Error>(self, path: String) -> Result<Self::Value, E> {
+                Ok(LibraryEntry(LibraryFolder {
+                    path,
+                    label: String::new(),
+                    content_id: None,
+                    total_size: None,
+                    update_clean_bytes_tally: None,
+                    time_last_update_verified: None,
+                    apps: BTreeMap::new()
+                }))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                LibraryFolder::deserialize(de::value::MapAccessDeserializer::new(map)).map(LibraryEntry)
+            }
+        }
+
+        deserializer.deserialize_any(EntryVisitor)
+    }
+}
+
+/// A single library within the libraryfolders.vdf
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LibraryFolder {
+    pub path: String,
+    #[serde(default)]
+    pub label: String,
+    #[serde(rename = "contentid", default)]
+    pub content_id: Option<u64>,
+    #[serde(rename = "totalsize", default)]
+    pub total_size: Option<u64>,
+    #[serde(default)]
+    pub update_clean_bytes_tally: Option<u64>,
+    #[serde(default)]
+    pub time_last_update_verified: Option<u64>,
+    /// The games installed in this library (according to steam), with their size on disk
+    #[serde(default)]
+    pub apps: BTreeMap<u32, u64>
+}
+
+/// The content of a `steamapps/appmanifest_<appid>.acf`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppManifestAcf {
+    #[serde(rename = "AppState")]
+    pub app_state: AppState
+}
+
+/// The state of an installed app, as noted in it's appmanifest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppState {
+    #[serde(rename = "appid")]
+    pub app_id: u32,
+    #[serde(default)]
+    pub universe: Option<u32>,
+    #[serde(rename = "LauncherPath", default)]
+    pub launcher_path: Option<String>,
+    pub name: String,
+    #[serde(rename = "StateFlags", default)]
+    pub state_flags: u32,
+    #[serde(rename = "installdir")]
+    pub install_dir: String,
+    #[serde(rename = "LastUpdated", default)]
+    pub last_updated: u64,
+    #[serde(rename = "SizeOnDisk", default)]
+    pub size_on_disk: u64,
+    #[serde(rename = "buildid", default)]
+    pub build_id: u64,
+    #[serde(rename = "LastOwner", default)]
+    pub last_owner: Option<u64>,
+    #[serde(rename = "InstalledDepots", default)]
+    pub installed_depots: BTreeMap<u32, InstalledDepot>,
+    #[serde(rename = "UserConfig", default)]
+    pub user_config: BTreeMap<String, String>,
+    #[serde(rename = "MountedConfig", default)]
+    pub mounted_config: BTreeMap<String, String>
+}
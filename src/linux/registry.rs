@@ -0,0 +1,1114 @@
+use std::{cell::OnceCell, cmp::Ordering, fs::{self, File}, io::{self, BufRead, BufReader}, os::unix::{fs::MetadataExt, net::UnixStream}, path::{Path, PathBuf}, sync::{Arc, Mutex, PoisonError}, time::{SystemTime, UNIX_EPOCH}};
+
+use crate::{vdf::write_file_atomic, ProtonFinderError};
+
+const TICKS_PER_SEC: u64 = 10_000_000;
+const TICKS_1601_TO_1970: u64 = 116_444_736_000_000_000;
+
+// Value types, as defined by windows
+pub const REG_NONE: u32 = 0;
+pub const REG_SZ: u32 = 1;
+pub const REG_EXPAND_SZ: u32 = 2;
+pub const REG_BINARY: u32 = 3;
+pub const REG_DWORD: u32 = 4;
+pub const REG_MULTI_SZ: u32 = 7;
+pub const REG_QWORD: u32 = 11;
+
+/// A typed value within a wine registry file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegValue {
+    /// REG_SZ, written as `"text"`
+    Str(String),
+    /// REG_EXPAND_SZ, a string containing `%VARIABLES%`, written as `str(2):"text"` or `hex(2):`
+    ExpandStr(String),
+    /// REG_MULTI_SZ, written as `str(7):"a\0b\0"` or `hex(7):`
+    MultiStr(Vec<String>),
+    /// REG_DWORD, written as `dword:0000002a`
+    Dword(u32),
+    /// REG_QWORD, written as `hex(b):` in little endian
+    Qword(u64),
+    /// REG_BINARY, written as `hex:`
+    Binary(Vec<u8>),
+    /// Any other type (or a string type with data that is not a terminated string), with it's raw data
+    Other { kind: u32, data: Vec<u8> }
+}
+
+impl RegValue {
+    /// The windows type number of this value (REG_SZ, REG_DWORD, etc.)
+    pub fn kind(&self) -> u32 {
+        match self {
+            RegValue::Str(_) => REG_SZ,
+            RegValue::ExpandStr(_) => REG_EXPAND_SZ,
+            RegValue::MultiStr(_) => REG_MULTI_SZ,
+            RegValue::Dword(_) => REG_DWORD,
+            RegValue::Qword(_) => REG_QWORD,
+            RegValue::Binary(_) => REG_BINARY,
+            RegValue::Other { kind, .. } => *kind
+        }
+    }
+
+    /// Returns the text of REG_SZ and REG_EXPAND_SZ values (without expanding the variables)
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            RegValue::Str(text) | RegValue::ExpandStr(text) => Some(text.as_str()),
+            _ => None
+        }
+    }
+
+    /// Returns the strings of a REG_MULTI_SZ
+    pub fn as_multi_str(&self) -> Option<&[String]> {
+        match self {
+            RegValue::MultiStr(list) => Some(list.as_slice()),
+            _ => None
+        }
+    }
+
+    /// Returns a REG_DWORD
+    pub fn as_u32(&self) -> Option<u32> {
+        match self {
+            RegValue::Dword(val) => Some(*val),
+            _ => None
+        }
+    }
+
+    /// Returns a REG_DWORD or REG_QWORD
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            RegValue::Dword(val) => Some(*val as u64),
+            RegValue::Qword(val) => Some(*val),
+            _ => None
+        }
+    }
+
+    /// Returns the data of REG_BINARY and all unknown types
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            RegValue::Binary(data) | RegValue::Other { data, .. } => Some(data.as_slice()),
+            _ => None
+        }
+    }
+
+    /// Builds the value from the type and the raw data, as stored by windows
+    pub fn from_raw(kind: u32, data: Vec<u8>) -> RegValue {
+        match kind {
+            REG_SZ | REG_EXPAND_SZ | REG_MULTI_SZ => {
+                let Some(text) = utf16_terminated(&data) else {
+                    return RegValue::Other { kind, data };
+                };
+
+                match kind {
+                    REG_SZ => RegValue::Str(text),
+                    REG_EXPAND_SZ => RegValue::ExpandStr(text),
+                    _ => RegValue::MultiStr(split_multi_str(&text))
+                }
+            },
+            REG_DWORD if data.len() == 4 => RegValue::Dword(u32::from_le_bytes([data[0], data[1], data[2], data[3]])),
+            REG_QWORD if data.len() == 8 => {
+                let mut bytes = [0_u8; 8];
+                bytes.copy_from_slice(&data);
+                RegValue::Qword(u64::from_le_bytes(bytes))
+            },
+            REG_BINARY => RegValue::Binary(data),
+            _ => RegValue::Other { kind, data }
+        }
+    }
+}
+
+/// Decodes UTF-16LE data that ends in a null terminator, returning the text without it
+fn utf16_terminated(data: &[u8]) -> Option<String> {
+    if data.len() < 2 || data.len() % 2 != 0 {
+        return None;
+    }
+
+    let chars: Vec<u16> = data.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+    let (last, text) = chars.split_last()?;
+    if *last != 0 {
+        return None;
+    }
+
+    Some(String::from_utf16_lossy(text))
+}
+
+/// The text of a REG_MULTI_SZ has every string terminated by a \0 (the final terminator of the
+/// list is already removed)
+fn split_multi_str(text: &str) -> Vec<String> {
+    if text.is_empty() {
+        return Vec::new();
+    }
+
+    let text = text.strip_suffix('\0').unwrap_or(text);
+    text.split('\0').map(str::to_string).collect()
+}
+
+/// Reads one logical line, joining lines that end in a `\` (used by wine to wrap hex data).
+///
+/// Returns the number of lines read, 0 on EOF
+fn read_logical_line<R: BufRead>(reader: &mut R, buf: &mut String) -> io::Result<usize> {
+    buf.clear();
+    let mut lines = 0;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(lines);
+        }
+        lines += 1;
+
+        // the following lines are indented
+        let part = match lines {
+            1 => line.trim_end(),
+            _ => line.trim()
+        };
+
+        match part.strip_suffix('\\') {
+            Some(part) => buf.push_str(part),
+            None => {
+                buf.push_str(part);
+                return Ok(lines);
+            }
+        }
+    }
+}
+
+/// Parses a quoted string (the opening `"` already stripped) up to the closing quote,
+/// resolving escape sequences.
+///
+/// Returns the text and the remainder after the closing quote
+fn parse_quoted(text: &str) -> Result<(String, &str), &'static str> {
+    parse_escaped(text, '"')
+}
+
+/// Parses a key header (the opening `[` already stripped) up to the closing bracket.
+///
+/// Returns the path and the remainder after the closing bracket
+fn parse_key_header(text: &str) -> Result<(String, &str), &'static str> {
+    parse_escaped(text, ']')
+}
+
+fn parse_escaped(text: &str, end: char) -> Result<(String, &str), &'static str> {
+    let mut res = Vec::<u16>::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            c if c == end => return Ok((String::from_utf16_lossy(&res), &text[index + 1..])),
+            '\\' => {
+                let Some((_, esc)) = chars.next() else {
+                    break;
+                };
+
+                match esc {
+                    'a' => res.push(0x07),
+                    'b' => res.push(0x08),
+                    'e' => res.push(0x1b),
+                    'f' => res.push(0x0c),
+                    'n' => res.push(b'\n' as u16),
+                    'r' => res.push(b'\r' as u16),
+                    't' => res.push(b'\t' as u16),
+                    'v' => res.push(0x0b),
+                    'x' => {
+                        // up to 4 hex digits
+                        let mut val = 0_u16;
+                        for _ in 0..4 {
+                            match chars.peek().and_then(|(_, c)| c.to_digit(16)) {
+                                Some(digit) => {
+                                    val = val * 16 + digit as u16;
+                                    chars.next();
+                                },
+                                None => break
+                            }
+                        }
+                        res.push(val);
+                    },
+                    '0'..='7' => {
+                        // up to 3 octal digits
+                        let mut val = esc.to_digit(8).unwrap_or_default() as u16;
+                        for _ in 0..2 {
+                            match chars.peek().and_then(|(_, c)| c.to_digit(8)) {
+                                Some(digit) => {
+                                    val = val * 8 + digit as u16;
+                                    chars.next();
+                                },
+                                None => break
+                            }
+                        }
+                        res.push(val);
+                    },
+                    other => {
+                        let mut buf = [0_u16; 2];
+                        res.extend_from_slice(other.encode_utf16(&mut buf));
+                    }
+                }
+            },
+            other => {
+                let mut buf = [0_u16; 2];
+                res.extend_from_slice(other.encode_utf16(&mut buf));
+            }
+        }
+    }
+
+    match end {
+        ']' => Err("unterminated key"),
+        _ => Err("unterminated string")
+    }
+}
+
+/// Parses a value line (`"name"=data` or `@=data`), returning the name ("" for the default value)
+fn parse_value_line(line: &str) -> Result<(String, RegValue), &'static str> {
+    let (name, rest) = if let Some(rest) = line.strip_prefix('@') {
+        (String::new(), rest)
+    } else if let Some(rest) = line.strip_prefix('"') {
+        parse_quoted(rest)?
+    } else {
+        return Err("expected value name");
+    };
+
+    let data = rest.trim_start().strip_prefix('=').ok_or("expected '=' after value name")?.trim_start();
+    Ok((name, parse_data(data)?))
+}
+
+/// Parses the data part of a value line
+fn parse_data(data: &str) -> Result<RegValue, &'static str> {
+    if let Some(rest) = data.strip_prefix('"') {
+        let (text, _) = parse_quoted(rest)?;
+        return Ok(RegValue::Str(text));
+    }
+
+    if let Some(rest) = data.strip_prefix("dword:") {
+        let val = u32::from_str_radix(rest.trim(), 16).map_err(|_| "invalid dword")?;
+        return Ok(RegValue::Dword(val));
+    }
+
+    if let Some(rest) = data.strip_prefix("str(") {
+        let (kind, rest) = rest.split_once("):").ok_or("invalid str type")?;
+        let kind = u32::from_str_radix(kind, 16).map_err(|_| "invalid str type")?;
+        let (text, _) = parse_quoted(rest.strip_prefix('"').ok_or("expected string")?)?;
+
+        return Ok(match kind {
+            REG_EXPAND_SZ => RegValue::ExpandStr(text),
+            REG_MULTI_SZ => RegValue::MultiStr(split_multi_str(&text)),
+            REG_SZ => RegValue::Str(text),
+            _ => {
+                let mut data = Vec::with_capacity(text.len() * 2 + 2);
+                for c in text.encode_utf16().chain(std::iter::once(0)) {
+                    data.extend_from_slice(&c.to_le_bytes());
+                }
+                RegValue::Other { kind, data }
+            }
+        });
+    }
+
+    let (kind, hex) = if let Some(rest) = data.strip_prefix("hex:") {
+        (REG_BINARY, rest)
+    } else if let Some(rest) = data.strip_prefix("hex(") {
+        let (kind, rest) = rest.split_once("):").ok_or("invalid hex type")?;
+        (u32::from_str_radix(kind, 16).map_err(|_| "invalid hex type")?, rest)
+    } else {
+        return Err("unknown value type");
+    };
+
+    let mut bytes = Vec::new();
+    for byte in hex.split(',').map(str::trim).filter(|b| !b.is_empty()) {
+        bytes.push(u8::from_str_radix(byte, 16).map_err(|_| "invalid hex data")?);
+    }
+
+    Ok(RegValue::from_raw(kind, bytes))
+}
+
+/// A registry file (like `user.reg`) loaded into memory.
+///
+/// All lookups ignore case, same as on windows.
+/// Paths are in the windows format (`Software\Wine`), double backslashes as written in the files
+/// are accepted too.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Registry {
+    relative_to: Option<String>,
+    arch: Option<String>,
+    root: RegKey
+}
+
+impl Registry {
+    /// Reads the registry file
+    pub fn open(path: &Path) -> Result<Registry, ProtonFinderError> {
+        let file = File::open(path)?;
+        Ok(Self::read(BufReader::new(file))?)
+    }
+
+    /// Parses the content of a registry file.
+    ///
+    /// Lines that can't be parsed are skipped (same as wine does)
+    pub fn parse(text: &str) -> Registry {
+        Self::read(text.as_bytes()).unwrap_or_default()
+    }
+
+    fn read<R: BufRead>(mut reader: R) -> io::Result<Registry> {
+        let mut reg = Registry::default();
+        // the current key, None before the first key
+        let mut current: Option<&mut RegKey> = None;
+
+        let mut line = String::new();
+        while read_logical_line(&mut reader, &mut line)? != 0 {
+            let trimed = line.trim();
+
+            if let Some(header) = trimed.strip_prefix('[') {
+                if let Ok((path, rest)) = parse_key_header(header) {
+                    let key = reg.root.create_path(split_path(&path), false);
+                    key.timestamp = rest.trim().parse().ok();
+                    current = Some(key);
+                }
+                continue;
+            }
+
+            let Some(key) = current.as_deref_mut() else {
+                // The header before the first key
+                if let Some(rest) = trimed.strip_prefix(";; All keys relative to ") {
+                    reg.relative_to = Some(rest.replace("\\\\", "\\"));
+                } else if let Some(arch) = trimed.strip_prefix("#arch=") {
+                    reg.arch = Some(arch.to_string());
+                }
+                continue;
+            };
+
+            if let Some(time) = trimed.strip_prefix("#time=") {
+                key.time = u64::from_str_radix(time, 16).ok();
+            } else if let Some(class) = trimed.strip_prefix("#class=\"") {
+                key.class = parse_quoted(class).ok().map(|(class, _)| class);
+            } else if trimed == "#link" {
+                key.link = true;
+            } else if let Ok((name, value)) = parse_value_line(trimed) {
+                key.insert_value(name, value);
+            }
+        }
+
+        Ok(reg)
+    }
+
+    /// The key all paths in this file are relative to (like `\User\S-1-5-21-0-0-0-1000`)
+    pub fn relative_to(&self) -> Option<&str> {
+        self.relative_to.as_deref()
+    }
+
+    /// The architecture of the prefix (`win32` or `win64`), as noted by the `#arch=` line
+    pub fn arch(&self) -> Option<&str> {
+        self.arch.as_deref()
+    }
+
+    /// The root of the key tree, it has no name and no values
+    pub fn root(&self) -> &RegKey {
+        &self.root
+    }
+
+    /// Returns the key at the path
+    pub fn key(&self, path: &str) -> Option<&RegKey> {
+        self.root.find_path(split_path(path))
+    }
+
+    /// Returns the names of the subkeys of the key at the path
+    pub fn subkeys(&self, path: &str) -> Option<Vec<&str>> {
+        Some(self.key(path)?.subkeys().map(RegKey::name).collect())
+    }
+
+    /// Returns the values of the key at the path, the default value (`@`) has an empty name
+    pub fn values(&self, path: &str) -> Option<&[(String, RegValue)]> {
+        Some(self.key(path)?.values())
+    }
+
+    /// Returns a single value of the key at the path
+    pub fn get_value(&self, path: &str, name: &str) -> Option<&RegValue> {
+        self.key(path)?.value(name)
+    }
+
+    /// Returns the key at the path for editing
+    pub fn key_mut(&mut self, path: &str) -> Option<&mut RegKey> {
+        self.root.find_path_mut(split_path(path))
+    }
+
+    /// Returns the key at the path, creating it (and all missing parents) if it doesn't exist
+    pub fn create_key(&mut self, path: &str) -> &mut RegKey {
+        self.root.create_path(split_path(path), true)
+    }
+
+    /// Removes the key at the path (and all it's subkeys), returning it
+    pub fn delete_key(&mut self, path: &str) -> Option<RegKey> {
+        let path = path.trim_end_matches('\\');
+        let (parent, name) = match path.rsplit_once('\\') {
+            Some((parent, name)) => (self.key_mut(parent)?, name),
+            None => (&mut self.root, path)
+        };
+        parent.delete_subkey(name)
+    }
+
+    /// Sets a value, creating the key if it doesn't exist. Use "" as name for the default value
+    pub fn set_value(&mut self, path: &str, name: &str, value: RegValue) {
+        self.create_key(path).set_value(name, value);
+    }
+
+    /// Removes a value, returning it
+    pub fn delete_value(&mut self, path: &str, name: &str) -> Option<RegValue> {
+        self.key_mut(path)?.delete_value(name)
+    }
+
+    /// Serializes the registry in the format wine writes
+    pub fn to_reg_string(&self) -> String {
+        let mut out = String::from("WINE REGISTRY Version 2\n");
+        if let Some(relative) = self.relative_to.as_ref() {
+            out.push_str(&format!(";; All keys relative to {}\n", relative.replace('\\', "\\\\")));
+        }
+        if let Some(arch) = self.arch.as_ref() {
+            out.push_str(&format!("\n#arch={}\n", arch));
+        }
+
+        self.root.write(&mut Vec::new(), true, &mut out);
+        out
+    }
+
+    /// Writes the registry to the file (replacing it).
+    ///
+    /// Wine keeps the registry in memory while the prefix is running, and overwrites the files
+    /// when it exits, so this refuses with Err(WineserverRunning) while the wineserver of the
+    /// prefix the file is in is running.
+    pub fn save(&self, path: &Path) -> Result<(), ProtonFinderError> {
+        if let Some(pfx) = path.parent() {
+            if wineserver_running(pfx) {
+                return Err(ProtonFinderError::WineserverRunning(pfx.to_path_buf()));
+            }
+        }
+
+        Ok(write_file_atomic(path, self.to_reg_string().as_bytes())?)
+    }
+}
+
+/// If the wineserver for this prefix is running.
+///
+/// Wine creates it's socket in `/tmp/.wine-<uid>/server-<dev>-<inode>/`, with the device and inode of
+/// the prefix folder. A wineserver that crashed or was killed leaves the socket behind, so we
+/// connect to it, and only a refused connection (or a missing socket) counts as not running
+pub(crate) fn wineserver_running(pfx: &Path) -> bool {
+    let (Ok(pfx), Ok(proc)) = (fs::metadata(pfx), fs::metadata("/proc/self")) else {
+        return false;
+    };
+
+    let mut socket = PathBuf::from(format!("/tmp/.wine-{}", proc.uid()));
+    socket.push(format!("server-{:x}-{:x}", pfx.dev(), pfx.ino()));
+    socket.push("socket");
+    match UnixStream::connect(&socket) {
+        Ok(_) => true,
+        Err(err) => !matches!(err.kind(), io::ErrorKind::ConnectionRefused | io::ErrorKind::NotFound)
+    }
+}
+
+/// A key within the registry, with it's values and subkeys
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RegKey {
+    name: String,
+    timestamp: Option<u64>,
+    time: Option<u64>,
+    class: Option<String>,
+    link: bool,
+    values: Vec<(String, RegValue)>,
+    subkeys: Vec<RegKey>
+}
+
+impl RegKey {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// When this key was last modified, in seconds since the unix epoch (the number after the key)
+    pub fn timestamp(&self) -> Option<u64> {
+        self.timestamp
+    }
+
+    /// When this key was last modified as a windows FILETIME (100ns since 1601), from the `#time=` line
+    pub fn modified_time(&self) -> Option<u64> {
+        self.time
+    }
+
+    /// The class of this key, from the `#class=` line
+    pub fn class(&self) -> Option<&str> {
+        self.class.as_deref()
+    }
+
+    /// If this key is a symbolic link (`#link`), the target is in the value `SymbolicLinkValue`
+    pub fn is_link(&self) -> bool {
+        self.link
+    }
+
+    pub fn values(&self) -> &[(String, RegValue)] {
+        &self.values
+    }
+
+    /// Returns the value with this name (case insensitive), "" for the default value
+    pub fn value(&self, name: &str) -> Option<&RegValue> {
+        self.values.iter().find(|(n, _)| eq_ignore_case(n, name)).map(|(_, val)| val)
+    }
+
+    pub fn subkeys(&self) -> impl Iterator<Item = &RegKey> {
+        self.subkeys.iter()
+    }
+
+    /// Returns the direct subkey with this name (case insensitive)
+    pub fn subkey(&self, name: &str) -> Option<&RegKey> {
+        self.subkeys.iter().find(|key| eq_ignore_case(&key.name, name))
+    }
+
+    /// Returns the key at the path relative to this key
+    pub fn key(&self, path: &str) -> Option<&RegKey> {
+        self.find_path(split_path(path))
+    }
+
+    fn find_path<'a, I: Iterator<Item = &'a str>>(&self, mut path: I) -> Option<&RegKey> {
+        match path.next() {
+            Some(name) => self.subkey(name)?.find_path(path),
+            None => Some(self)
+        }
+    }
+
+    fn find_path_mut<'a, I: Iterator<Item = &'a str>>(&mut self, mut path: I) -> Option<&mut RegKey> {
+        match path.next() {
+            Some(name) => self.subkey_mut(name)?.find_path_mut(path),
+            None => Some(self)
+        }
+    }
+
+    /// Walks the path, creating the missing keys.
+    /// If touch is set the created keys (and their parents) have their time set to now
+    fn create_path<'a, I: Iterator<Item = &'a str>>(&mut self, path: I, touch: bool) -> &mut RegKey {
+        let mut key = self;
+        for name in path {
+            let index = match key.subkeys.binary_search_by(|sub| compare_names(&sub.name, name)) {
+                Ok(index) => index,
+                Err(index) => {
+                    // Wine keeps the keys sorted, so we insert in order
+                    key.subkeys.insert(index, RegKey { name: name.to_string(), ..Default::default() });
+                    if touch {
+                        key.touch();
+                        key.subkeys[index].touch();
+                    }
+                    index
+                }
+            };
+            key = &mut key.subkeys[index];
+        }
+
+        key
+    }
+
+    fn insert_value(&mut self, name: String, value: RegValue) {
+        match self.values.binary_search_by(|(n, _)| compare_names(n, &name)) {
+            Ok(index) => self.values[index].1 = value,
+            Err(index) => self.values.insert(index, (name, value))
+        }
+    }
+
+    /// Sets the modification time to now
+    fn touch(&mut self) {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        self.timestamp = Some(now.as_secs());
+        self.time = Some(now.as_secs() * TICKS_PER_SEC + now.subsec_nanos() as u64 / 100 + TICKS_1601_TO_1970);
+    }
+
+    /// Returns the direct subkey with this name (case insensitive)
+    pub fn subkey_mut(&mut self, name: &str) -> Option<&mut RegKey> {
+        self.subkeys.iter_mut().find(|key| eq_ignore_case(&key.name, name))
+    }
+
+    /// Returns the subkey with this name, creating it if it doesn't exist
+    pub fn create_subkey(&mut self, name: &str) -> &mut RegKey {
+        self.create_path(split_path(name), true)
+    }
+
+    /// Removes the subkey (and all it's subkeys), returning it
+    pub fn delete_subkey(&mut self, name: &str) -> Option<RegKey> {
+        let index = self.subkeys.iter().position(|key| eq_ignore_case(&key.name, name))?;
+        self.touch();
+        Some(self.subkeys.remove(index))
+    }
+
+    /// Sets the value (replacing it if it exists), use "" for the default value
+    pub fn set_value(&mut self, name: &str, value: RegValue) {
+        self.insert_value(name.to_string(), value);
+        self.touch();
+    }
+
+    /// Removes the value, returning it
+    pub fn delete_value(&mut self, name: &str) -> Option<RegValue> {
+        let index = self.values.iter().position(|(n, _)| eq_ignore_case(n, name))?;
+        self.touch();
+        Some(self.values.remove(index).1)
+    }
+
+    /// Writes this key and it's subkeys in the format of wine
+    fn write(&self, path: &mut Vec<String>, is_root: bool, out: &mut String) {
+        // Wine only writes keys that have values, no subkeys or special options.
+        // Keys with only subkeys are implied by the subkeys
+        if !self.values.is_empty() || self.subkeys.is_empty() || self.class.is_some() || self.link {
+            out.push_str("\n[");
+            if !is_root {
+                let escaped: Vec<String> = path.iter().map(|name| escape_str(name, ['[', ']'])).collect();
+                out.push_str(&escaped.join("\\\\"));
+            }
+
+            let time = self.time.or_else(|| self.timestamp.map(|secs| secs * TICKS_PER_SEC + TICKS_1601_TO_1970)).unwrap_or_default();
+            let timestamp = self.timestamp.unwrap_or(time.saturating_sub(TICKS_1601_TO_1970) / TICKS_PER_SEC);
+            out.push_str(&format!("] {}\n#time={:x}{:08x}\n", timestamp, time >> 32, time & 0xffffffff));
+
+            if let Some(class) = self.class.as_ref() {
+                out.push_str(&format!("#class=\"{}\"\n", escape_str(class, ['"', '"'])));
+            }
+            if self.link {
+                out.push_str("#link\n");
+            }
+
+            for (name, value) in self.values.iter() {
+                write_value(name, value, out);
+            }
+        }
+
+        for sub in self.subkeys.iter() {
+            path.push(sub.name.clone());
+            sub.write(path, false, out);
+            path.pop();
+        }
+    }
+}
+
+/// Writes a value line, wrapping hex data the same way wine does
+fn write_value(name: &str, value: &RegValue, out: &mut String) {
+    let start = out.len();
+    match name.is_empty() {
+        true => out.push_str("@="),
+        false => out.push_str(&format!("\"{}\"=", escape_str(name, ['"', '"'])))
+    }
+
+    let data = match value {
+        RegValue::Str(text) => {
+            out.push_str(&format!("\"{}\"\n", escape_str(text, ['"', '"'])));
+            return;
+        },
+        RegValue::ExpandStr(text) => {
+            out.push_str(&format!("str(2):\"{}\"\n", escape_str(text, ['"', '"'])));
+            return;
+        },
+        RegValue::MultiStr(list) => {
+            let text: String = list.iter().flat_map(|item| item.chars().chain(std::iter::once('\0'))).collect();
+            out.push_str(&format!("str(7):\"{}\"\n", escape_str(&text, ['"', '"'])));
+            return;
+        },
+        RegValue::Dword(val) => {
+            out.push_str(&format!("dword:{:08x}\n", val));
+            return;
+        },
+        RegValue::Qword(val) => val.to_le_bytes().to_vec(),
+        RegValue::Binary(data) | RegValue::Other { data, .. } => data.clone()
+    };
+
+    match value.kind() {
+        REG_BINARY => out.push_str("hex:"),
+        kind => out.push_str(&format!("hex({:x}):", kind))
+    }
+
+    let mut count = out.len() - start;
+    for (i, byte) in data.iter().enumerate() {
+        out.push_str(&format!("{:02x}", byte));
+        count += 2;
+        if i < data.len() - 1 {
+            out.push(',');
+            count += 1;
+            if count > 76 {
+                out.push_str("\\\n  ");
+                count = 2;
+            }
+        }
+    }
+    out.push('\n');
+}
+
+/// Escapes a string the way wine does, escape are the additional characters that need a backslash
+fn escape_str(text: &str, escape: [char; 2]) -> String {
+    const ESCAPES: &[u8; 32] = b".......abtnvfr.............e....";
+    let chars: Vec<u16> = text.encode_utf16().collect();
+    let mut out = String::with_capacity(chars.len());
+
+    for (i, c) in chars.iter().copied().enumerate() {
+        let next = chars.get(i + 1).copied();
+        if c > 127 {
+            match next {
+                Some(next) if next < 128 && (next as u8).is_ascii_hexdigit() => out.push_str(&format!("\\x{:04x}", c)),
+                _ => out.push_str(&format!("\\x{:x}", c))
+            }
+        } else if c < 32 {
+            match ESCAPES[c as usize] {
+                b'.' => match next {
+                    Some(next) if (b'0' as u16..=b'7' as u16).contains(&next) => out.push_str(&format!("\\{:03o}", c)),
+                    _ => out.push_str(&format!("\\{:o}", c))
+                },
+                esc => {
+                    out.push('\\');
+                    out.push(esc as char);
+                }
+            }
+        } else {
+            let c = c as u8 as char;
+            if c == '\\' || escape.contains(&c) {
+                out.push('\\');
+            }
+            out.push(c);
+        }
+    }
+
+    out
+}
+
+/// Compares key or value names the way wine sorts them
+fn compare_names(a: &str, b: &str) -> Ordering {
+    lowercase_utf16(a).cmp(lowercase_utf16(b))
+}
+
+/// The lowercase utf16 units of the name, without allocating
+fn lowercase_utf16(name: &str) -> impl Iterator<Item = u16> + '_ {
+    name.chars().flat_map(char::to_lowercase).flat_map(|c| {
+        let mut buf = [0_u16; 2];
+        let len = c.encode_utf16(&mut buf).len();
+        buf.into_iter().take(len)
+    })
+}
+
+/// Splits a path into the key names, accepting single and double backslashes
+fn split_path(path: &str) -> impl Iterator<Item = &str> {
+    path.split('\\').filter(|name| !name.is_empty())
+}
+
+/// Compares names ignoring case, used for key and value names as well as file names
+pub(crate) fn eq_ignore_case(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b) || a.chars().flat_map(char::to_lowercase).eq(b.chars().flat_map(char::to_lowercase))
+}
+
+const MAX_EXPAND_DEPTH: usize = 8;
+
+/// The keys environment variables are read from, in order of priority
+const ENV_KEYS: [&str; 3] = [
+    "HKCU\\Volatile Environment",
+    "HKCU\\Environment",
+    "HKLM\\System\\CurrentControlSet\\Control\\Session Manager\\Environment"
+];
+
+/// Variables wine sets on process start from other keys: (variable, key, value)
+const ENV_DERIVED: [(&str, &str, &str); 7] = [
+    ("ProgramFiles", "HKLM\\Software\\Microsoft\\Windows\\CurrentVersion", "ProgramFilesDir"),
+    ("ProgramFiles(x86)", "HKLM\\Software\\Microsoft\\Windows\\CurrentVersion", "ProgramFilesDir (x86)"),
+    ("ProgramW6432", "HKLM\\Software\\Microsoft\\Windows\\CurrentVersion", "ProgramW6432Dir"),
+    ("CommonProgramFiles", "HKLM\\Software\\Microsoft\\Windows\\CurrentVersion", "CommonFilesDir"),
+    ("CommonProgramFiles(x86)", "HKLM\\Software\\Microsoft\\Windows\\CurrentVersion", "CommonFilesDir (x86)"),
+    ("CommonProgramW6432", "HKLM\\Software\\Microsoft\\Windows\\CurrentVersion", "CommonW6432Dir"),
+    ("SystemRoot", "HKLM\\Software\\Microsoft\\Windows NT\\CurrentVersion", "SystemRoot")
+];
+
+/// The registry files of a prefix, mapped to the root keys they contain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Hive {
+    /// `HKEY_CURRENT_USER`, stored in `user.reg`
+    CurrentUser,
+    /// `HKEY_LOCAL_MACHINE`, stored in `system.reg`
+    LocalMachine,
+    /// `HKEY_USERS\.Default`, stored in `userdef.reg`
+    DefaultUser
+}
+
+impl Hive {
+    /// The name of the file within the prefix folder
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            Hive::CurrentUser => "user.reg",
+            Hive::LocalMachine => "system.reg",
+            Hive::DefaultUser => "userdef.reg"
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            Hive::CurrentUser => 0,
+            Hive::LocalMachine => 1,
+            Hive::DefaultUser => 2
+        }
+    }
+}
+
+/// The registry of a prefix, accessed through windows style paths like `HKLM\Software\Valve\Steam`.
+///
+/// Supported roots are `HKEY_CURRENT_USER` (`HKCU`), `HKEY_LOCAL_MACHINE` (`HKLM`) and
+/// `HKEY_USERS` (`HKU`) with `.Default` or the SID of the user.
+/// On 64-bit prefixes keys missing under `HKLM\Software` are looked up in `HKLM\Software\Wow6432Node`
+/// (where 32-bit programs, like steam, write their keys).
+///
+/// The files are only read when a key in them is first accessed.
+#[derive(Debug)]
+pub struct PrefixRegistry {
+    pfx: PathBuf,
+    cache: Arc<HiveCache>,
+    hives: [OnceCell<Option<Arc<Registry>>>; 3],
+    /// The state of the hives before the first edit (or the last save), to only write the changed ones
+    saved: [Option<Arc<Registry>>; 3]
+}
+
+/// The parsed registry files of a prefix, shared between all `PrefixRegistry` of a `ProtonPrefix`.
+///
+/// A file is parsed again once it's modification time changed (like when wine wrote it on exit)
+#[derive(Debug, Default)]
+pub(crate) struct HiveCache {
+    hives: Mutex<[CachedHive; 3]>
+}
+
+/// A parsed file, with the modification time of the file when it was read
+type CachedHive = Option<(SystemTime, Arc<Registry>)>;
+
+impl HiveCache {
+    fn load(&self, pfx: &Path, hive: Hive) -> Option<Arc<Registry>> {
+        let path = pfx.join(hive.file_name());
+        let modified = fs::metadata(&path).and_then(|meta| meta.modified()).ok()?;
+
+        let mut hives = self.hives.lock().unwrap_or_else(PoisonError::into_inner);
+        let entry = &mut hives[hive.index()];
+        match entry {
+            Some((time, reg)) if *time == modified => Some(reg.clone()),
+            _ => {
+                let reg = Arc::new(Registry::open(&path).ok()?);
+                *entry = Some((modified, reg.clone()));
+                Some(reg)
+            }
+        }
+    }
+
+    /// Notes the registry that was just written to the file
+    fn store(&self, pfx: &Path, hive: Hive, reg: Arc<Registry>) {
+        let modified = fs::metadata(pfx.join(hive.file_name())).and_then(|meta| meta.modified());
+
+        let mut hives = self.hives.lock().unwrap_or_else(PoisonError::into_inner);
+        hives[hive.index()] = modified.ok().map(|time| (time, reg));
+    }
+}
+
+impl PrefixRegistry {
+    pub(crate) fn new(pfx: PathBuf, cache: Arc<HiveCache>) -> PrefixRegistry {
+        PrefixRegistry { pfx, cache, hives: Default::default(), saved: Default::default() }
+    }
+
+    /// Returns the loaded registry file, None if it does not exist (or could not be read)
+    pub fn hive(&self, hive: Hive) -> Option<&Registry> {
+        self.hives[hive.index()].get_or_init(|| self.cache.load(&self.pfx, hive)).as_deref()
+    }
+
+    /// Returns the loaded registry file for editing, the changes are written with `save`
+    pub fn hive_mut(&mut self, hive: Hive) -> Option<&mut Registry> {
+        self.hive(hive)?;
+        let reg = self.hives[hive.index()].get_mut()?.as_mut()?;
+        if self.saved[hive.index()].is_none() {
+            self.saved[hive.index()] = Some(reg.clone());
+        }
+
+        // The loaded file is shared, so this edits a copy
+        Some(Arc::make_mut(reg))
+    }
+
+    /// If this is a 64-bit prefix
+    pub fn is_win64(&self) -> bool {
+        self.hive(Hive::LocalMachine).and_then(Registry::arch) == Some("win64")
+    }
+
+    /// Splits a full path into the hive and the path within
+    pub fn resolve<'a>(&self, path: &'a str) -> Option<(Hive, &'a str)> {
+        let path = path.trim_start_matches('\\');
+        let (root, rest) = path.split_once('\\').unwrap_or((path, ""));
+        let rest = rest.trim_start_matches('\\');
+
+        match root.to_ascii_uppercase().as_str() {
+            "HKEY_CURRENT_USER" | "HKCU" => Some((Hive::CurrentUser, rest)),
+            "HKEY_LOCAL_MACHINE" | "HKLM" => Some((Hive::LocalMachine, rest)),
+            "HKEY_USERS" | "HKU" => {
+                let (user, rest) = rest.split_once('\\').unwrap_or((rest, ""));
+                let rest = rest.trim_start_matches('\\');
+                if user.eq_ignore_ascii_case(".Default") {
+                    return Some((Hive::DefaultUser, rest));
+                }
+
+                // user.reg notes the sid of the user as `\User\<sid>`
+                let sid = self.hive(Hive::CurrentUser)?.relative_to()?.rsplit('\\').next()?;
+                match user.eq_ignore_ascii_case(sid) {
+                    true => Some((Hive::CurrentUser, rest)),
+                    false => None
+                }
+            },
+            _ => None
+        }
+    }
+
+    /// Returns the key at the path
+    pub fn key(&self, path: &str) -> Option<&RegKey> {
+        let (hive, sub) = self.resolve(path)?;
+        let reg = self.hive(hive)?;
+        if let Some(key) = reg.key(sub) {
+            return Some(key);
+        }
+
+        reg.key(&self.redirect(hive, sub)?)
+    }
+
+    /// Returns the names of the subkeys of the key at the path
+    pub fn subkeys(&self, path: &str) -> Option<Vec<&str>> {
+        Some(self.key(path)?.subkeys().map(RegKey::name).collect())
+    }
+
+    /// Returns the values of the key at the path, the default value (`@`) has an empty name
+    pub fn values(&self, path: &str) -> Option<&[(String, RegValue)]> {
+        Some(self.key(path)?.values())
+    }
+
+    /// Returns a single value of the key at the path
+    pub fn get_value(&self, path: &str, name: &str) -> Option<&RegValue> {
+        self.key(path)?.value(name)
+    }
+
+    /// Sets a value, creating the key if it doesn't exist (no redirection is applied).
+    ///
+    /// Returns false if the path is not within a hive, or the registry file doesn't exist
+    pub fn set_value(&mut self, path: &str, name: &str, value: RegValue) -> bool {
+        let Some((hive, sub)) = self.resolve(path) else {
+            return false;
+        };
+        let sub = sub.to_string();
+
+        match self.hive_mut(hive) {
+            Some(reg) => {
+                reg.set_value(&sub, name, value);
+                true
+            },
+            None => false
+        }
+    }
+
+    /// Removes a value, returning it (no redirection is applied)
+    pub fn delete_value(&mut self, path: &str, name: &str) -> Option<RegValue> {
+        let (hive, sub) = self.resolve(path)?;
+        let sub = sub.to_string();
+        self.hive_mut(hive)?.delete_value(&sub, name)
+    }
+
+    /// Writes the files that were edited.
+    ///
+    /// Refuses with Err(WineserverRunning) while the prefix is running, see `Registry::save`
+    pub fn save(&mut self) -> Result<(), ProtonFinderError> {
+        for hive in [Hive::CurrentUser, Hive::LocalMachine, Hive::DefaultUser] {
+            let (Some(Some(reg)), Some(saved)) = (self.hives[hive.index()].get(), self.saved[hive.index()].as_ref()) else {
+                continue;
+            };
+            if Arc::ptr_eq(reg, saved) || reg == saved {
+                continue;
+            }
+
+            let reg = reg.clone();
+            reg.save(&self.pfx.join(hive.file_name()))?;
+            self.cache.store(&self.pfx, hive, reg.clone());
+            self.saved[hive.index()] = Some(reg);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the value of an environment variable (case insensitive) as the programs within the
+    /// prefix see it, with variables within the value expanded.
+    ///
+    /// The variables are read from `HKCU\Volatile Environment`, `HKCU\Environment` and
+    /// `HKLM\...\Session Manager\Environment` (in this order), and some are derived like wine does
+    /// (`ProgramFiles`, `SystemRoot`, `SystemDrive`, etc.)
+    pub fn env_var(&self, name: &str) -> Option<String> {
+        self.env_var_depth(name, 0, &|_| None, &mut true)
+    }
+
+    /// Replaces all `%VARIABLE%` in the text with their values, unknown variables are kept as is
+    /// (the same as `ExpandEnvironmentStrings` on windows)
+    pub fn expand_env(&self, text: &str) -> String {
+        self.expand_env_or(text, |_| None)
+    }
+
+    /// Same as `expand_env`, but variables that are not set within the prefix are looked up
+    /// with the fallback (like a table of wine's defaults)
+    pub fn expand_env_or<F: Fn(&str) -> Option<String>>(&self, text: &str, fallback: F) -> String {
+        self.expand_env_depth(text, 0, &fallback, &mut true)
+    }
+
+    /// Same as `expand_env_or`, but returns None if any variable could not be resolved
+    pub(crate) fn try_expand_env_or<F: Fn(&str) -> Option<String>>(&self, text: &str, fallback: F) -> Option<String> {
+        let mut complete = true;
+        let res = self.expand_env_depth(text, 0, &fallback, &mut complete);
+        complete.then_some(res)
+    }
+
+    fn env_var_depth(&self, name: &str, depth: usize, fallback: &dyn Fn(&str) -> Option<String>, complete: &mut bool) -> Option<String> {
+        // Values that are not strings (like a REG_DWORD) are treated the same as missing ones
+        let value = ENV_KEYS.iter()
+            .find_map(|key| self.get_value(key, name)?.as_str())
+            .or_else(|| {
+                let (key, value) = ENV_DERIVED.iter().find(|(var, _, _)| var.eq_ignore_ascii_case(name)).map(|(_, key, value)| (key, value))?;
+                self.get_value(key, value)?.as_str()
+            });
+
+        match value {
+            Some(value) => Some(self.expand_env_depth(value, depth + 1, fallback, complete)),
+            None if name.eq_ignore_ascii_case("SystemDrive") => Some("C:".to_string()),
+            None => fallback(name).map(|value| self.expand_env_depth(&value, depth + 1, fallback, complete))
+        }
+    }
+
+    /// Expands the text, `complete` is set to false if a variable is kept as is
+    fn expand_env_depth(&self, text: &str, depth: usize, fallback: &dyn Fn(&str) -> Option<String>, complete: &mut bool) -> String {
+        if depth > MAX_EXPAND_DEPTH {
+            *complete &= !text.contains('%');
+            return text.to_string();
+        }
+
+        let mut res = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find('%') {
+            res.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+
+            let Some(end) = after.find('%') else {
+                rest = &rest[start..];
+                break;
+            };
+
+            let name = &after[..end];
+            match (!name.is_empty()).then(|| self.env_var_depth(name, depth, fallback, complete)).flatten() {
+                Some(value) => {
+                    res.push_str(&value);
+                    rest = &after[end + 1..];
+                },
+                None => {
+                    // The closing % could be the start of the next variable
+                    *complete &= name.is_empty();
+                    res.push('%');
+                    res.push_str(name);
+                    rest = &after[end..];
+                }
+            }
+        }
+
+        res.push_str(rest);
+        res
+    }
+
+    /// The path 32-bit programs see on 64-bit prefixes
+    fn redirect(&self, hive: Hive, sub: &str) -> Option<String> {
+        if hive != Hive::LocalMachine || !self.is_win64() {
+            return None;
+        }
+
+        let (software, rest) = sub.split_once('\\')?;
+        if !software.eq_ignore_ascii_case("Software") || rest.to_ascii_lowercase().starts_with("wow6432node") {
+            return None;
+        }
+
+        Some(format!("{}\\Wow6432Node\\{}", software, rest))
+    }
+}
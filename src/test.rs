@@ -1,6 +1,51 @@
 
+/// A unique folder within the temp directory for a single test, removed again once dropped
+#[cfg(target_os = "linux")]
+struct TestDir(std::path::PathBuf);
+
+#[cfg(target_os = "linux")]
+impl TestDir {
+    fn new(name: &str) -> TestDir {
+        let mut path = std::env::temp_dir();
+        path.push(format!("proton-finder-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        TestDir(path)
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl std::ops::Deref for TestDir {
+    type Target = std::path::Path;
+
+    fn deref(&self) -> &std::path::Path {
+        &self.0
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Sets up a minimal prefix: `dosdevices/c:` links to `drive_c` (containing the folders),
+/// and user.reg has the content given
+#[cfg(target_os = "linux")]
+fn create_prefix(pfx: &std::path::Path, folders: &[&str], user_reg: &str) {
+    std::fs::create_dir_all(pfx.join("dosdevices")).unwrap();
+    std::fs::create_dir_all(pfx.join("drive_c")).unwrap();
+    for dir in folders {
+        std::fs::create_dir_all(pfx.join("drive_c").join(dir)).unwrap();
+    }
+    std::os::unix::fs::symlink("../drive_c", pfx.join("dosdevices/c:")).unwrap();
+    std::fs::write(pfx.join("user.reg"), user_reg).unwrap();
+}
+
 #[cfg(target_os = "linux")]
 #[test]
+#[allow(deprecated)]
 pub fn find_steam_root() {
     // We test if a steam root can be found at all
     // As such we ignore errors from incorrect $STEAM_DIR
@@ -15,6 +60,78 @@ pub fn find_steam_root() {
 
 #[cfg(target_os = "linux")]
 #[test]
+pub fn locate_steam_root() {
+    // We test if a steam root can be found at all, with incorrect $STEAM_DIR only being a warning
+    //
+    // This test fails if steam is not installed
+    assert!(crate::linux::locate_steam_root().ok().is_some(), "Unable to find any steam install");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn steam_locator_custom_root() {
+    // We only search the root we already found, passed in as a custom root
+    // Missing custom roots are warnings, and don't stop the search
+    use crate::linux::SteamLocator;
+
+    let root = crate::linux::locate_steam_root().ok().expect("Unable to find any steam install");
+    let res = SteamLocator::new()
+        .use_env(false)
+        .locations([])
+        .add_root("/this/path/does/not/exist")
+        .add_root(root.get_root())
+        .locate_all();
+
+    assert_eq!(res.warnings.len(), 1);
+    let roots = res.result.expect("Custom root was not found");
+    assert_eq!(roots.len(), 1);
+    assert_eq!(roots[0].get_root(), root.get_root());
+
+    // Nothing to search
+    let res = SteamLocator::new().use_env(false).locations([]).locate_all();
+    assert!(matches!(res.result, Err(crate::ProtonFinderError::NoSteamRoot)));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn steam_root_source() {
+    // Steam creates ~/.steam/steam as a symlink to the actual install
+    use crate::linux::{SteamLocation, SteamLocator, SteamRootSource};
+
+    let root = SteamLocator::new().use_env(false).locations([SteamLocation::DotSteam]).locate().ok().expect("Unable to find ~/.steam/steam");
+    assert_eq!(root.source(), SteamRootSource::Symlink);
+
+    let custom = crate::linux::steam_root_from(root.get_root()).expect("Root is no longer a steam root");
+    assert_eq!(custom.source(), SteamRootSource::Custom);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn steam_root_dedup() {
+    // The same root passed in through multiple paths is only returned once,
+    // unless deduplication is disabled
+    use crate::linux::SteamLocator;
+    use std::collections::HashSet;
+
+    let root = crate::linux::locate_steam_root().ok().expect("Unable to find any steam install");
+    let mut trailing = root.get_root().into_os_string();
+    trailing.push("/");
+
+    let locator = SteamLocator::new().use_env(false).locations([]).add_root(root.get_root()).add_root(trailing);
+    assert_eq!(locator.locate_all().ok().expect("Custom root was not found").len(), 1);
+
+    let roots = locator.dedup(false).locate_all().ok().expect("Custom root was not found");
+    assert_eq!(roots.len(), 2);
+    assert_eq!(roots[0], roots[1]);
+    assert_eq!(roots.into_iter().collect::<HashSet<_>>().len(), 1);
+
+    let libs = root.get_libraries();
+    assert_eq!(libs.iter().collect::<HashSet<_>>().len(), libs.len(), "Duplicate libraries");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+#[allow(deprecated)]
 pub fn read_libraries_file_manual() {
     // We test the vdf parser on the libraries file 
     //
@@ -37,6 +154,7 @@ pub fn read_libraries_file_manual() {
 
 #[cfg(target_os = "linux")]
 #[test]
+#[allow(deprecated)]
 pub fn read_libraries_file() {
     // We test the vdf parser on the libraries file 
     //
@@ -54,6 +172,446 @@ pub fn read_libraries_file() {
 
 #[cfg(target_os = "linux")]
 #[test]
+pub fn parse_vdf_text_format() {
+    // We test the vdf parser on the parts of the format steam doesn't write itself,
+    // but that appear in files written by hand/other tools
+    //
+    // This test has no requirements
+    let text = "// comment at the start\n\
+        \"root\" {\n\
+            \"escaped\"\t\"say \\\"hi\\\"\\\\\" // trailing comment\n\
+            unquoted value\n\
+            \"nested\" { \"inner\" \"1\" }\n\
+            \"windows\" \"yes\" [$WIN32]\n\
+            \"linux\" \"yes\" [$LINUX || $OSX]\n\
+            \"block\" [!$WIN32] { }\n\
+        }";
+
+    let res = crate::linux::parse_vdf_str(text);
+    assert!(res.is_some(), "Failed to parse vdf text");
+    let res = res.unwrap();
+
+    let root = match res.get("root") {
+        Some(crate::linux::VdfValue::Complex(root)) => root,
+        _ => panic!("root struct missing")
+    };
+    assert!(matches!(root.get("escaped"), Some(crate::linux::VdfValue::Simple(v)) if v == "say \"hi\"\\"), "Escape sequences not parsed");
+    assert!(matches!(root.get("unquoted"), Some(crate::linux::VdfValue::Simple(v)) if v == "value"), "Unquoted tokens not parsed");
+    assert!(matches!(root.get("nested"), Some(crate::linux::VdfValue::Complex(_))), "Same line brackets not parsed");
+    assert!(!root.contains_key("windows"), "False conditional was not skipped");
+    assert!(root.contains_key("linux"), "True conditional was skipped");
+    assert!(root.contains_key("block"), "Conditional on struct was skipped");
+
+    assert!(crate::linux::parse_vdf_str("\"key\" {").is_none(), "Unclosed struct was accepted");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn parse_vdf_error_line() {
+    // We test that syntax errors are reported with their line
+    //
+    // This test has no requirements
+    let res = crate::linux::try_parse_vdf_str("\"root\"\n{\n\t\"key\"\t\"value\"\n\t}\n}");
+    assert!(matches!(res, Err(crate::ProtonFinderError::Parse { line: 5, .. })), "Expected parse error at line 5, got {:?}", res);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn vdf_duplicate_keys() {
+    // We test that the order and duplicate keys are kept
+    //
+    // This test has no requirements
+    let res = crate::linux::parse_vdf_str("\"b\" \"1\"\n\"a\" \"2\"\n\"B\" \"3\"\n\"b\" \"4\"");
+    assert!(res.is_some(), "Failed to parse vdf text");
+    let res = res.unwrap();
+
+    let keys: Vec<&str> = res.keys().map(|k| k.as_str()).collect();
+    assert_eq!(keys, ["b", "a", "B", "b"], "Order of keys not preserved");
+    assert_eq!(res.get_all("b").count(), 2, "Duplicate keys were not preserved");
+    assert_eq!(res.get_all_ignore_case("b").count(), 3, "Case insensitive lookup failed");
+    assert!(matches!(res.get("b"), Some(crate::linux::VdfValue::Simple(v)) if v == "4"), "get did not return the last value");
+    assert!(matches!(res.get_ignore_case("b"), Some(crate::linux::VdfValue::Simple(v)) if v == "4"), "get_ignore_case did not return the last value");
+    assert!(res.get("A").is_none(), "get is not case sensitive");
+    assert!(res.get_ignore_case("A").is_some(), "Case insensitive lookup failed");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn vdf_round_trip() {
+    // We test that text formated like steam does it is written back byte for byte,
+    // and that the atomic write leaves only the target file behind
+    //
+    // This test requires a writable temp directory
+    let text = "\"libraryfolders\"\n{\n\t\"0\"\n\t{\n\t\t\"path\"\t\t\"C:\\\\Program Files (x86)\\\\Steam\"\n\t\t\"label\"\t\t\"\"\n\t\t\"apps\"\n\t\t{\n\t\t\t\"2420510\"\t\t\"302134023\"\n\t\t}\n\t}\n}\n";
+
+    let res = crate::linux::parse_vdf_str(text);
+    assert!(res.is_some(), "Failed to parse vdf text");
+    let res = res.unwrap();
+    assert_eq!(res.to_vdf_string(), text, "Serialized vdf differs from the input");
+
+    let dir = TestDir::new("vdf-write");
+    let path = dir.join("libraryfolders.vdf");
+
+    assert!(crate::linux::write_vdf_file(&path, &res).is_ok(), "Failed to write vdf file");
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), text, "Written vdf file differs from the input");
+    assert_eq!(std::fs::read_dir(&*dir).unwrap().count(), 1, "Temporary file was left behind");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn vdf_round_trip_values() {
+    // Files not written by steam are not kept byte for byte, but their values survive the round trip
+    // (comments, unquoted tokens and unknown escapes are normalized, false conditionals dropped)
+    //
+    // This test has no requirements
+    let text = "// comment\nlibraryfolders\n{\n    \"path\" \"C:\\Games\" // unknown escape\n    \"win\" \"1\" [$WIN32]\n    \"lin\" \"1\" [$LINUX]\n}\n";
+
+    let res = crate::linux::parse_vdf_str(text).expect("Failed to parse vdf text");
+    let written = res.to_vdf_string();
+    assert_eq!(written, "\"libraryfolders\"\n{\n\t\"path\"\t\t\"C:\\\\Games\"\n\t\"lin\"\t\t\"1\"\n}\n");
+
+    let reread = crate::linux::parse_vdf_str(&written).expect("Failed to parse written vdf");
+    assert_eq!(reread, res, "Values changed in the round trip");
+    assert_eq!(reread.get_path("libraryfolders/path").and_then(crate::linux::VdfValue::as_str), Ok("C:\\Games"));
+    assert_eq!(reread.to_vdf_string(), written, "Normalized output is not stable");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn vdf_get_path() {
+    // We test the path query api and the typed accessors
+    //
+    // This test has no requirements
+    use crate::linux::VdfAccessError;
+
+    let res = crate::linux::parse_vdf_str("\"UserLocalConfigStore\" { \"Software\" { \"valve\" { \"Steam\" { \"apps\" { \"2420510\" { \"LaunchOptions\" \"-dx11\" \"Playtime\" \"42\" \"cloud\" \"1\" } } } } } }");
+    assert!(res.is_some(), "Failed to parse vdf text");
+    let res = res.unwrap();
+
+    let app = res.get_path("UserLocalConfigStore/Software/Valve/Steam/apps/2420510");
+    assert!(app.is_ok(), "Failed to resolve path: {:?}", app.err());
+    let app = app.unwrap().as_struct().unwrap();
+    assert_eq!(app.get_path("LaunchOptions").and_then(|v| v.as_str()), Ok("-dx11"));
+    assert_eq!(app.get_path("Playtime").and_then(|v| v.as_u64()), Ok(42));
+    assert_eq!(app.get_path("cloud").and_then(|v| v.as_bool()), Ok(true));
+
+    assert_eq!(res.get_path("UserLocalConfigStore/Software/Other").err(), Some(VdfAccessError::Missing("UserLocalConfigStore/Software/Other".to_string())));
+    assert_eq!(app.get_path("LaunchOptions/deeper").err(), Some(VdfAccessError::NotAStruct("LaunchOptions".to_string())));
+    assert!(matches!(app.get_path("LaunchOptions").and_then(|v| v.as_u64()), Err(VdfAccessError::Unparsable { .. })));
+    assert!(matches!(app.get_path("LaunchOptions").and_then(|v| v.as_struct()), Err(VdfAccessError::WrongType { .. })));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn parse_vdf_include() {
+    // We test the #include and #base directives, which are resolved relative to the file
+    //
+    // This test requires a writable temp directory
+    let dir = TestDir::new("vdf-include");
+
+    let base = dir.join("base.vdf");
+    std::fs::write(&base, "\"root\" { \"a\" \"base\" \"b\" \"base\" }").unwrap();
+
+    let main = dir.join("main.vdf");
+    std::fs::write(&main, "#base \"base.vdf\"\n\"root\" { \"a\" \"main\" }").unwrap();
+
+    let res = crate::linux::parse_vdf_file(&main);
+    assert!(res.is_some(), "Failed to parse vdf file with #base");
+    let res = res.unwrap();
+
+    let root = match res.get("root") {
+        Some(crate::linux::VdfValue::Complex(root)) => root,
+        _ => panic!("root struct missing")
+    };
+    assert!(matches!(root.get("a"), Some(crate::linux::VdfValue::Simple(v)) if v == "main"), "#base overwrote a value");
+    assert!(matches!(root.get("b"), Some(crate::linux::VdfValue::Simple(v)) if v == "base"), "#base was not merged");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn parse_binary_vdf() {
+    // We test the binary vdf parser on data laid out like shortcuts.vdf
+    //
+    // This test has no requirements
+    let mut data = Vec::<u8>::new();
+    data.extend(b"\x00shortcuts\x00\x000\x00");
+    data.extend(b"\x02appid\x00");
+    data.extend((-1234i32).to_le_bytes());
+    data.extend(b"\x01AppName\x00Game\x00");
+    data.extend(b"\x07LastPlayTime\x00");
+    data.extend(1700000000u64.to_le_bytes());
+    data.extend(b"\x06color\x00\x01\x02\x03\x04");
+    data.extend(b"\x08\x08\x08");
+
+    let res = crate::linux::vdf::binary::parse_binary_vdf(&data);
+    assert!(res.is_some(), "Failed to parse binary vdf");
+    let res = res.unwrap();
+
+    let entry = match res.get("shortcuts") {
+        Some(crate::linux::VdfValue::Complex(shortcuts)) => match shortcuts.get("0") {
+            Some(crate::linux::VdfValue::Complex(entry)) => entry.clone(),
+            _ => panic!("shortcut entry missing")
+        },
+        _ => panic!("shortcuts struct missing")
+    };
+    assert_eq!(entry.get("appid"), Some(&crate::linux::VdfValue::Int32(-1234)), "int32 value not parsed");
+    assert_eq!(entry.get("AppName"), Some(&crate::linux::VdfValue::Simple("Game".to_string())), "string value not parsed");
+    assert_eq!(entry.get("LastPlayTime"), Some(&crate::linux::VdfValue::UInt64(1700000000)), "uint64 value not parsed");
+    assert_eq!(entry.get("color"), Some(&crate::linux::VdfValue::Color([1, 2, 3, 4])), "color value not parsed");
+
+    assert!(crate::linux::vdf::binary::parse_binary_vdf(b"\x00unterminated\x00\x01a\x00b\x00").is_none(), "Unterminated section was accepted");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn parse_appinfo_string_table() {
+    // We test reading an appinfo.vdf in version 29, where keys are stored in a string table
+    //
+    // This test requires a writable temp directory
+    let mut kv = Vec::<u8>::new();
+    kv.push(0x00);
+    kv.extend(0u32.to_le_bytes()); // "appinfo"
+    kv.push(0x02);
+    kv.extend(1u32.to_le_bytes()); // "appid"
+    kv.extend(2420510u32.to_le_bytes());
+    kv.extend(b"\x08\x08");
+
+    let mut entry = Vec::<u8>::new();
+    entry.extend(2u32.to_le_bytes()); // info_state
+    entry.extend(1700000000u32.to_le_bytes()); // last_updated
+    entry.extend(0u64.to_le_bytes()); // pics_token
+    entry.extend([0u8; 20]); // sha1
+    entry.extend(42u32.to_le_bytes()); // change_number
+    entry.extend([0u8; 20]); // binary sha1
+    entry.extend(kv);
+
+    let mut data = Vec::<u8>::new();
+    data.extend(0x07564429u32.to_le_bytes());
+    data.extend(1u32.to_le_bytes());
+    data.extend(0u64.to_le_bytes()); // string table offset, filled in below
+    data.extend(2420510u32.to_le_bytes());
+    data.extend((entry.len() as u32).to_le_bytes());
+    data.extend(entry);
+    data.extend(0u32.to_le_bytes());
+    let offset = data.len() as u64;
+    data[8..16].copy_from_slice(&offset.to_le_bytes());
+    data.extend(2u32.to_le_bytes());
+    data.extend(b"appinfo\x00appid\x00");
+
+    let dir = TestDir::new("appinfo");
+    let path = dir.join("appinfo.vdf");
+    std::fs::write(&path, data).unwrap();
+
+    let res = crate::linux::vdf::binary::read_appinfo_file(&path);
+    assert!(res.is_some(), "Failed to parse appinfo");
+    let res = res.unwrap();
+    assert_eq!(res.version, 29);
+    assert_eq!(res.apps.len(), 1);
+
+    let entry = crate::linux::vdf::binary::read_appinfo_entry(&path, 2420510);
+    assert!(entry.is_some(), "Failed to find app in appinfo");
+    let entry = entry.unwrap();
+    assert_eq!(entry.change_number, 42);
+    match entry.data.get("appinfo") {
+        Some(crate::linux::VdfValue::Complex(info)) => assert_eq!(info.get("appid"), Some(&crate::linux::VdfValue::Int32(2420510))),
+        _ => panic!("appinfo struct missing")
+    }
+
+    // Apps missing in the file are left out
+    let entries = crate::linux::vdf::binary::read_appinfo_entries(&path, &[2420510, 891390]);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].app_id, 2420510);
+    assert!(crate::linux::vdf::binary::read_appinfo_entry(&path, 891390).is_none());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn steam_root_cloud_save_locations() {
+    // The ufs section of the appinfo is resolved within the prefix, and through the rootoverrides
+    // for the native version
+    //
+    // This test requires a writable temp directory
+    let root = TestDir::new("steam-cloud");
+    let pfx = root.join("steamapps/compatdata/2420510/pfx");
+    for dir in ["ubuntu12_32", "config", "appcache", "steamapps/common/HoloCure"] {
+        std::fs::create_dir_all(root.join(dir)).unwrap();
+    }
+    create_prefix(&pfx, &["users/steamuser/AppData/Local"], "WINE REGISTRY Version 2\n");
+    std::fs::write(root.join("steamapps/appmanifest_2420510.acf"), "\"AppState\"\n{\n\t\"appid\"\t\t\"2420510\"\n\t\"installdir\"\t\t\"HoloCure\"\n}\n").unwrap();
+    std::fs::write(root.join("config/loginusers.vdf"), "\"users\"\n{\n\t\"76561197960287930\"\n\t{\n\t\t\"AccountName\"\t\t\"holo\"\n\t\t\"MostRecent\"\t\t\"1\"\n\t}\n}\n").unwrap();
+
+    let section = |name: &str, body: Vec<u8>| [vec![0x00], name.as_bytes().to_vec(), vec![0x00], body, vec![0x08]].concat();
+    let string = |key: &str, val: &str| [vec![0x01], key.as_bytes().to_vec(), vec![0x00], val.as_bytes().to_vec(), vec![0x00]].concat();
+    let ufs = section("ufs", [
+        section("savefiles", [
+            section("0", [string("root", "WinAppDataLocal"), string("path", "HoloCure/{Steam3AccountID}"), string("pattern", "*.dat"),
+                section("platforms", string("1", "Windows"))].concat()),
+            section("1", [string("root", "gameinstall"), string("path", "saves"), string("pattern", "*"), string("recursive", "1")].concat()),
+            section("2", [string("root", "MacHome"), string("path", "HoloCure"), string("pattern", "*")].concat()),
+            section("3", [string("root", "WindowsHome"), string("path", "HoloCure"), string("pattern", "*"),
+                section("platforms", string("1", "Windows"))].concat()),
+            section("4", [string("root", "SteamCloudDocuments"), string("path", ""), string("pattern", "*.sav"),
+                section("platforms", string("1", "Linux"))].concat())
+        ].concat()),
+        section("rootoverrides", [
+            section("0", [string("root", "gameinstall"), string("os", "Linux"), string("oscompare", "="), string("useinstead", "LinuxXdgDataHome"),
+                string("addpath", "HoloCure"), section("pathtransforms", section("0", [string("find", "saves"), string("replace", "save")].concat()))].concat())
+        ].concat())
+    ].concat());
+    let kv = [section("appinfo", [string("appid", "2420510"), ufs].concat()), vec![0x08]].concat();
+
+    let mut data = Vec::<u8>::new();
+    data.extend(0x07564428u32.to_le_bytes());
+    data.extend(1u32.to_le_bytes());
+    data.extend(2420510u32.to_le_bytes());
+    data.extend(((4 + 4 + 8 + 20 + 4 + 20 + kv.len()) as u32).to_le_bytes());
+    data.extend([0u8; 4 + 4 + 8 + 20 + 4 + 20]);
+    data.extend(kv);
+    data.extend(0u32.to_le_bytes());
+    std::fs::write(root.join("appcache/appinfo.vdf"), data).unwrap();
+
+    let steam = crate::linux::steam_root_from(root.to_path_buf()).expect("Temp steam root not valid");
+    let res = steam.cloud_save_locations(2420510);
+    assert_eq!(res.len(), 6, "{:?}", res);
+    assert_eq!(res[0].root, "WinAppDataLocal");
+    assert_eq!(res[0].dir, Some(pfx.join("drive_c/users/steamuser/AppData/Local").canonicalize().unwrap().join("HoloCure/22202")));
+    assert_eq!(res[0].pattern, "*.dat");
+    assert!(!res[0].recursive);
+    assert_eq!(res[1].root, "gameinstall");
+    assert_eq!(res[1].dir, Some(root.join("steamapps/common/HoloCure/saves")));
+    assert!(res[1].recursive);
+    assert_eq!(res[2].root, "LinuxXdgDataHome");
+    assert_eq!(res[2].dir, Some(dirs::data_dir().unwrap().join("HoloCure/save")));
+
+    // Mac roots can't be resolved, but are still reported
+    assert_eq!(res[3].root, "MacHome");
+    assert_eq!(res[3].dir, None);
+    assert_eq!(res[4].root, "WindowsHome");
+    assert_eq!(res[4].dir, Some(pfx.join("drive_c/users/steamuser").canonicalize().unwrap().join("HoloCure")));
+    assert_eq!(res[5].root, "SteamCloudDocuments");
+    assert_eq!(res[5].dir, Some(dirs::home_dir().unwrap().join(".SteamCloud/holo/HoloCure")));
+
+    assert!(steam.cloud_save_locations(1).is_empty());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn steam_root_compat_tool() {
+    // The tool is read from the CompatToolMapping, falling back to app 0,
+    // the version that created the prefix from compatdata
+    //
+    // This test requires a writable temp directory
+    let root = TestDir::new("steam-compat");
+    let pfx = root.join("steamapps/compatdata/2420510/pfx");
+    for dir in ["ubuntu12_32", "config", "compatibilitytools.d/GE-Proton9-1/files"] {
+        std::fs::create_dir_all(root.join(dir)).unwrap();
+    }
+    create_prefix(&pfx, &[], "WINE REGISTRY Version 2\n");
+    std::fs::write(root.join("steamapps/compatdata/2420510/version"), "GE-Proton9-1\n").unwrap();
+    std::fs::write(root.join("steamapps/compatdata/2420510/config_info"), concat!(
+        "GE-Proton9-1\n",
+        "/opt/tools/GE-Proton9-1/files/share/fonts/\n",
+        "/opt/tools/GE-Proton9-1/files/lib/wine/dxvk/\n"
+    )).unwrap();
+    std::fs::write(root.join("compatibilitytools.d/GE-Proton9-1/compatibilitytool.vdf"), concat!(
+        "\"compatibilitytools\"\n{\n\t\"compat_tools\"\n\t{\n\t\t\"GE-Proton9-1\"\n\t\t{\n",
+        "\t\t\t\"install_path\" \".\"\n\t\t\t\"display_name\" \"GE-Proton 9-1\"\n",
+        "\t\t\t\"from_oslist\" \"windows\"\n\t\t\t\"to_oslist\" \"linux\"\n\t\t}\n\t}\n}\n"
+    )).unwrap();
+    std::fs::write(root.join("config/config.vdf"), concat!(
+        "\"InstallConfigStore\"\n{\n\t\"Software\"\n\t{\n\t\t\"Valve\"\n\t\t{\n\t\t\t\"steam\"\n\t\t\t{\n",
+        "\t\t\t\t\"CompatToolMapping\"\n\t\t\t\t{\n",
+        "\t\t\t\t\t\"0\"\n\t\t\t\t\t{\n\t\t\t\t\t\t\"name\" \"proton_experimental\"\n\t\t\t\t\t\t\"config\" \"\"\n\t\t\t\t\t\t\"priority\" \"75\"\n\t\t\t\t\t}\n",
+        "\t\t\t\t\t\"2420510\"\n\t\t\t\t\t{\n\t\t\t\t\t\t\"name\" \"GE-Proton9-1\"\n\t\t\t\t\t\t\"config\" \"\"\n\t\t\t\t\t\t\"priority\" \"250\"\n\t\t\t\t\t}\n",
+        "\t\t\t\t}\n\t\t\t}\n\t\t}\n\t}\n}\n"
+    )).unwrap();
+
+    let steam = crate::linux::steam_root_from(root.to_path_buf()).expect("Temp steam root not valid");
+    let tool = steam.compat_tool_for(2420510).expect("No tool found");
+    assert_eq!(tool.name, "GE-Proton9-1");
+    assert_eq!(tool.display_name, "GE-Proton 9-1");
+    assert_eq!(tool.install_dir, Some(root.join("compatibilitytools.d/GE-Proton9-1").canonicalize().unwrap()));
+    assert!(!tool.is_default);
+    assert_eq!(tool.prefix_version.as_deref(), Some("GE-Proton9-1"));
+    assert_eq!(tool.prefix_tool_dir, Some(std::path::PathBuf::from("/opt/tools/GE-Proton9-1")));
+
+    // Not installed (and no appinfo), so only the name is known
+    let tool = steam.compat_tool_for(1).expect("Default tool not used");
+    assert_eq!(tool.name, "proton_experimental");
+    assert_eq!(tool.display_name, "proton_experimental");
+    assert_eq!(tool.install_dir, None);
+    assert!(tool.is_default);
+    assert_eq!(tool.prefix_version, None);
+
+    // The default only applies to games without a Linux version
+    let section = |name: &str, body: Vec<u8>| [vec![0x00], name.as_bytes().to_vec(), vec![0x00], body, vec![0x08]].concat();
+    let string = |key: &str, val: &str| [vec![0x01], key.as_bytes().to_vec(), vec![0x00], val.as_bytes().to_vec(), vec![0x00]].concat();
+    let mut data = Vec::<u8>::new();
+    data.extend(0x07564428u32.to_le_bytes());
+    data.extend(1u32.to_le_bytes());
+    for (app_id, oslist) in [(2u32, "windows,macos,linux"), (3, "windows")] {
+        let kv = [section("appinfo", [string("appid", &app_id.to_string()), section("common", string("oslist", oslist))].concat()), vec![0x08]].concat();
+        data.extend(app_id.to_le_bytes());
+        data.extend(((4 + 4 + 8 + 20 + 4 + 20 + kv.len()) as u32).to_le_bytes());
+        data.extend([0u8; 4 + 4 + 8 + 20 + 4 + 20]);
+        data.extend(kv);
+    }
+    data.extend(0u32.to_le_bytes());
+    std::fs::create_dir_all(root.join("appcache")).unwrap();
+    std::fs::write(root.join("appcache/appinfo.vdf"), data).unwrap();
+    assert!(steam.compat_tool_for(2).is_none(), "Default applied to a native game");
+    assert_eq!(steam.compat_tool_for(3).map(|tool| tool.name).as_deref(), Some("proton_experimental"));
+
+    std::fs::write(root.join("config/config.vdf"), "\"InstallConfigStore\"\n{\n}\n").unwrap();
+    assert!(steam.compat_tool_for(2420510).is_none());
+}
+
+#[cfg(all(target_os = "linux", feature = "serde"))]
+#[test]
+pub fn serde_library_folders() {
+    // We test deserializing the libraryfolders format into the typed model and writing it back
+    //
+    // This test has no requirements
+    let text = "\"libraryfolders\"\n{\n\t\"0\"\n\t{\n\t\t\"path\"\t\t\"/home/user/.local/share/Steam\"\n\t\t\"label\"\t\t\"\"\n\t\t\"contentid\"\t\t\"4363347016395342398\"\n\t\t\"apps\"\n\t\t{\n\t\t\t\"2420510\"\t\t\"302134023\"\n\t\t}\n\t}\n}\n";
+
+    let res: Result<crate::linux::models::LibraryFoldersVdf, _> = crate::linux::vdf::from_str(text);
+    assert!(res.is_ok(), "Failed to deserialize libraryfolders: {:?}", res.err());
+    let res = res.unwrap();
+
+    let lib = res.libraries.get(&0);
+    assert!(lib.is_some(), "Library 0 missing");
+    let lib = lib.unwrap();
+    assert_eq!(lib.path, "/home/user/.local/share/Steam");
+    assert_eq!(lib.content_id, Some(4363347016395342398));
+    assert_eq!(lib.apps.get(&2420510), Some(&302134023));
+
+    let out = crate::linux::vdf::to_string(&res);
+    assert_eq!(out, Ok(text.to_string()), "Serialized libraryfolders differs from the input");
+}
+
+#[cfg(all(target_os = "linux", feature = "serde"))]
+#[test]
+pub fn serde_library_folders_old_layout() {
+    // We test reading the older libraryfolders layout (only paths, plus stats keys), and that duplicate keys keep the last
+    //
+    // This test has no requirements
+    let text = "\"LibraryFolders\"\n{\n\t\"TimeNextStatsReport\"\t\t\"1700000000\"\n\t\"ContentStatsID\"\t\t\"-4363347016395342398\"\n\t\"1\"\t\t\"/mnt/games/SteamLibrary\"\n}\n";
+
+    let res: Result<crate::linux::models::LibraryFoldersVdf, _> = crate::linux::vdf::from_str(text);
+    assert!(res.is_ok(), "Failed to deserialize old libraryfolders: {:?}", res.err());
+    let res = res.unwrap();
+    assert_eq!(res.libraries.len(), 1);
+    assert_eq!(res.libraries.get(&1).map(|lib| lib.path.as_str()), Some("/mnt/games/SteamLibrary"));
+
+    let text = "\"libraryfolders\"\n{\n\t\"0\"\n\t{\n\t\t\"path\"\t\t\"/old\"\n\t\t\"path\"\t\t\"/new\"\n\t}\n}\n";
+    let res: Result<crate::linux::models::LibraryFoldersVdf, _> = crate::linux::vdf::from_str(text);
+    assert!(res.is_ok(), "Failed to deserialize libraryfolders with duplicate keys: {:?}", res.err());
+    assert_eq!(res.unwrap().libraries.get(&0).map(|lib| lib.path.clone()), Some("/new".to_string()));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+#[allow(deprecated)]
 pub fn find_install_library() {
     // We try to find the library in which HoloCure is installed 
     //
@@ -70,6 +628,521 @@ pub fn find_install_library() {
 }
 
 #[cfg(target_os = "linux")]
+#[test]
+pub fn locate_install_library() {
+    // We try to find the library in which HoloCure is installed, through the steam root from locate_steam_root
+    //
+    // As such this fails if steam is not installed, and if the vdf parses failed
+    let root = crate::linux::locate_steam_root().ok();
+    assert!(root.is_some(), "Unable to find any steam install");
+    let root = root.unwrap();
+
+    let res = root.get_install_library(2420510);
+    assert!(res.is_some(), "Install location of Holocure not found, is it not installed?");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn read_holocure_app_manifest() {
+    // We read the appmanifest of HoloCure from the library it is installed in
+    //
+    // As such this fails if steam and HoloCure are not installed
+    let root = crate::linux::locate_steam_root().ok();
+    assert!(root.is_some(), "Unable to find any steam install");
+    let lib = root.unwrap().get_install_library(2420510);
+    assert!(lib.is_some(), "Install location of Holocure not found, is it not installed?");
+    let lib = lib.unwrap();
+
+    let manifest = lib.get_app_manifest(2420510);
+    assert!(manifest.is_some(), "Failed to read HoloCure appmanifest");
+    let manifest = manifest.unwrap();
+    assert_eq!(manifest.app_id, 2420510);
+    assert!(!manifest.install_dir.is_empty(), "installdir missing from HoloCure appmanifest");
+
+    assert!(lib.installed_apps().iter().any(|app| app.app_id == 2420510), "HoloCure missing from installed apps");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn parse_reg_value_types() {
+    // We test all value types wine writes, including wrapped hex data and escapes
+    //
+    // This test requires a writable temp directory
+    use crate::linux::{RegParser, RegValue};
+
+    let dir = TestDir::new("reg-types");
+    let path = dir.join("user.reg");
+    std::fs::write(&path, concat!(
+        "WINE REGISTRY Version 2\n",
+        ";; All keys relative to \\\\User\\\\S-1-5-21-0-0-0-1000\n\n",
+        "#arch=win64\n\n",
+        "[Software\\\\Test] 1700000000\n",
+        "#time=1da1b2c3d4e5f60\n",
+        "@=\"default\"\n",
+        "\"Str\"=\"C:\\\\users\\\\steamuser \\\"quoted\\\" \\x00e9\"\n",
+        "\"Dword\"=dword:0000002a\n",
+        "\"Binary\"=hex:01,02,\\\n  03,ff\n",
+        "\"Expand\"=hex(2):25,00,41,00,25,00,00,00\n",
+        "\"ExpandStr\"=str(2):\"%USERPROFILE%\\\\Documents\"\n",
+        "\"Multi\"=hex(7):61,00,00,00,62,00,00,00,00,00\n",
+        "\"MultiStr\"=str(7):\"a\\0b\\0\"\n",
+        "\"Qword\"=hex(b):01,00,00,00,00,00,00,01\n",
+        "\"Unknown\"=hex(100):ab\n",
+        "\n",
+        "[Software\\\\Other] 1700000000\n",
+        "\"Str\"=\"other\"\n"
+    )).unwrap();
+
+    let reg = RegParser::new(path).expect("Failed to open reg file");
+    let values = reg.open_key_values("Software\\Test").expect("Key not found");
+    let get = |name: &str| values.iter().find(|(n, _)| n == name).map(|(_, v)| v.clone());
+
+    assert_eq!(values.len(), 10);
+    assert_eq!(get(""), Some(RegValue::Str("default".to_string())));
+    assert_eq!(get("Str"), Some(RegValue::Str("C:\\users\\steamuser \"quoted\" \u{e9}".to_string())));
+    assert_eq!(get("Dword"), Some(RegValue::Dword(42)));
+    assert_eq!(get("Binary"), Some(RegValue::Binary(vec![1, 2, 3, 255])));
+    assert_eq!(get("Expand"), Some(RegValue::ExpandStr("%A%".to_string())));
+    assert_eq!(get("ExpandStr"), Some(RegValue::ExpandStr("%USERPROFILE%\\Documents".to_string())));
+    assert_eq!(get("Multi"), Some(RegValue::MultiStr(vec!["a".to_string(), "b".to_string()])));
+    assert_eq!(get("MultiStr"), Some(RegValue::MultiStr(vec!["a".to_string(), "b".to_string()])));
+    assert_eq!(get("Qword"), Some(RegValue::Qword(0x0100000000000001)));
+    assert_eq!(get("Unknown"), Some(RegValue::Other { kind: 0x100, data: vec![0xab] }));
+
+    // The string only view, for compatibility
+    let map = reg.open_key("software\\\\test").expect("Key not found (case insensitive)");
+    assert_eq!(map.get("Dword").map(String::as_str), Some(""));
+    assert_eq!(map.get("ExpandStr").map(String::as_str), Some("%USERPROFILE%\\Documents"));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn registry_tree() {
+    // We test enumerating keys, case insensitive lookup and the metadata
+    use crate::linux::{RegValue, Registry};
+
+    let reg = Registry::parse(concat!(
+        "WINE REGISTRY Version 2\n",
+        ";; All keys relative to \\\\Machine\n\n",
+        "#arch=win64\n\n",
+        "[Software\\\\Microsoft\\\\Windows\\\\CurrentVersion\\\\Uninstall\\\\Game A] 1700000000\n",
+        "#time=1da1b2c3d4e5f60\n",
+        "\"DisplayName\"=\"Game A\"\n\n",
+        "[Software\\\\Microsoft\\\\Windows\\\\CurrentVersion\\\\Uninstall\\\\Game B] 1700000001\n",
+        "#class=\"Uninstaller\"\n",
+        "\"DisplayName\"=\"Game B\"\n",
+        "\"EstimatedSize\"=dword:00000400\n"
+    ));
+
+    assert_eq!(reg.arch(), Some("win64"));
+    assert_eq!(reg.relative_to(), Some("\\Machine"));
+
+    let uninstall = "software\\microsoft\\windows\\currentversion\\uninstall";
+    assert_eq!(reg.subkeys(uninstall), Some(vec!["Game A", "Game B"]));
+    assert_eq!(reg.get_value(&format!("{}\\game b", uninstall), "estimatedsize"), Some(&RegValue::Dword(1024)));
+
+    let key = reg.key("Software\\\\Microsoft\\\\Windows\\\\CurrentVersion\\\\Uninstall\\\\Game A").expect("Key not found");
+    assert_eq!(key.timestamp(), Some(1700000000));
+    assert_eq!(key.modified_time(), Some(0x1da1b2c3d4e5f60));
+    assert_eq!(key.value("DisplayName").and_then(RegValue::as_str), Some("Game A"));
+
+    let key = reg.root().key(&format!("{}\\Game B", uninstall)).expect("Key not found");
+    assert_eq!(key.class(), Some("Uninstaller"));
+    assert_eq!(key.values().len(), 2);
+    assert!(reg.key("Software\\Missing").is_none());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn registry_write() {
+    // A registry written by wine is serialized back exactly the same,
+    // and edits keep the keys sorted
+    //
+    // This test requires a writable temp directory
+    use crate::linux::{RegValue, Registry};
+
+    let text = concat!(
+        "WINE REGISTRY Version 2\n",
+        ";; All keys relative to \\\\User\\\\S-1-5-21-0-0-0-1000\n",
+        "\n#arch=win64\n",
+        "\n[Software\\\\Wine\\\\DllOverrides] 1700000000\n",
+        "#time=1da16e0a1b2c3d4\n",
+        "\"d3d11\"=\"native,builtin\"\n",
+        "\n[Software\\\\Wine\\\\Test] 1700000001\n",
+        "#time=1da16e0a1b2c3d5\n",
+        "@=\"default \\\"quoted\\\" C:\\\\path \\x00e9a\\x3a9\"\n",
+        "\"Binary\"=hex:HEXDATA\n",
+        "\"Dword\"=dword:0000002a\n",
+        "\"Expand\"=str(2):\"%USERPROFILE%\"\n",
+        "\"Multi\"=str(7):\"a\\0b\\0\"\n",
+        "\"Qword\"=hex(b):01,00,00,00,00,00,00,00\n"
+    ).replace("HEXDATA", "00,01,02,03,04,05,06,07,08,09,0a,0b,0c,0d,0e,0f,10,11,12,13,14,15,\\\n  16,17,18,19,1a,1b,1c,1d");
+
+    let mut reg = Registry::parse(&text);
+    assert_eq!(reg.to_reg_string(), text);
+
+    reg.set_value("Software\\Wine\\Direct3D", "renderer", RegValue::Str("vulkan".to_string()));
+    reg.set_value("Software\\Wine\\DllOverrides", "dxgi", RegValue::Str("native".to_string()));
+    assert_eq!(reg.subkeys("Software\\Wine"), Some(vec!["Direct3D", "DllOverrides", "Test"]));
+    assert_eq!(reg.delete_value("Software\\Wine\\DllOverrides", "D3D11"), Some(RegValue::Str("native,builtin".to_string())));
+    assert!(reg.delete_key("Software\\Wine\\Test").is_some());
+    reg.create_key("Temp");
+    assert!(reg.delete_key("Temp\\").is_some(), "Trailing backslash not ignored");
+    assert!(reg.key("Temp").is_none());
+
+    // The permissions of the file are kept when it is replaced
+    use std::os::unix::fs::PermissionsExt;
+    let dir = TestDir::new("reg-write");
+    let path = dir.join("user.reg");
+    std::fs::write(&path, "").unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o640)).unwrap();
+    reg.save(&path).expect("Failed to save registry");
+    assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o640);
+
+    // Saving is refused while the wineserver of the folder listens, but not for a stale socket
+    use std::os::unix::fs::MetadataExt;
+    let (meta, uid) = (std::fs::metadata(&*dir).unwrap(), std::fs::metadata("/proc/self").unwrap().uid());
+    let server = std::path::PathBuf::from(format!("/tmp/.wine-{}/server-{:x}-{:x}", uid, meta.dev(), meta.ino()));
+    std::fs::create_dir_all(&server).unwrap();
+    let listener = std::os::unix::net::UnixListener::bind(server.join("socket")).unwrap();
+    assert!(matches!(reg.save(&path), Err(crate::ProtonFinderError::WineserverRunning(_))), "Saved while the wineserver is running");
+    drop(listener);
+    let res = reg.save(&path);
+    let _ = std::fs::remove_dir_all(&server);
+    assert!(res.is_ok(), "Stale wineserver socket blocked saving: {:?}", res.err());
+
+    let reg = Registry::open(&path).expect("Failed to read written registry");
+    assert_eq!(reg.get_value("software\\wine\\direct3d", "Renderer"), Some(&RegValue::Str("vulkan".to_string())));
+    assert_eq!(reg.values("software\\wine\\dlloverrides").map(|v| v.len()), Some(1));
+    assert!(reg.key("Software\\Wine\\Test").is_none());
+    assert!(reg.key("Software\\Wine\\DllOverrides").and_then(|key| key.timestamp()).unwrap_or_default() > 1700000000);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn prefix_registry_view() {
+    // We build a minimal 64-bit prefix and access its hives through HKEY paths
+    //
+    // This test requires a writable temp directory
+    use crate::linux::{Hive, ProtonPrefix, RegValue};
+
+    let pfx = TestDir::new("prefix-registry");
+    create_prefix(&pfx, &[], concat!(
+        "WINE REGISTRY Version 2\n",
+        ";; All keys relative to \\\\User\\\\S-1-5-21-0-0-0-1000\n",
+        "\n#arch=win64\n",
+        "\n[Software\\\\Wine] 1700000000\n",
+        "\"Version\"=\"win10\"\n"
+    ));
+    std::fs::write(pfx.join("system.reg"), concat!(
+        "WINE REGISTRY Version 2\n",
+        ";; All keys relative to \\\\Machine\n",
+        "\n#arch=win64\n",
+        "\n[Software\\\\Wow6432Node\\\\Valve\\\\Steam] 1700000000\n",
+        "\"InstallPath\"=\"C:\\\\Program Files (x86)\\\\Steam\"\n"
+    )).unwrap();
+    std::fs::write(pfx.join("userdef.reg"), concat!(
+        "WINE REGISTRY Version 2\n",
+        ";; All keys relative to \\\\User\\\\.Default\n",
+        "\n#arch=win64\n",
+        "\n[Control Panel\\\\Desktop] 1700000000\n",
+        "\"Wallpaper\"=\"\"\n"
+    )).unwrap();
+
+    let prefix = ProtonPrefix::from_path(pfx.to_path_buf()).expect("Failed to open prefix");
+    let mut reg = prefix.registry();
+
+    assert!(reg.is_win64());
+    assert_eq!(reg.get_value("HKLM\\Software\\Valve\\Steam", "InstallPath").and_then(RegValue::as_str), Some("C:\\Program Files (x86)\\Steam"));
+    assert_eq!(reg.get_value("HKEY_CURRENT_USER\\Software\\Wine", "Version").and_then(RegValue::as_str), Some("win10"));
+    assert_eq!(reg.get_value("HKU\\S-1-5-21-0-0-0-1000\\Software\\Wine", "Version").and_then(RegValue::as_str), Some("win10"));
+    assert_eq!(reg.subkeys("HKEY_USERS\\.DEFAULT\\Control Panel"), Some(vec!["Desktop"]));
+    assert!(reg.key("HKCR\\.txt").is_none());
+
+    // The parsed files are shared, an edit only changes the registry it was made in
+    assert!(std::ptr::eq(reg.hive(Hive::CurrentUser).unwrap(), prefix.registry().hive(Hive::CurrentUser).unwrap()));
+    assert!(reg.set_value("HKCU\\Software\\Wine\\DllOverrides", "dxgi", RegValue::Str("native".to_string())));
+    assert!(prefix.registry().get_value("HKCU\\Software\\Wine\\DllOverrides", "dxgi").is_none());
+    reg.save().expect("Failed to save registry");
+
+    // Only edited hives are written, getting one for editing alone does not count
+    let written = std::fs::metadata(pfx.join("system.reg")).unwrap().modified().unwrap();
+    assert!(reg.hive_mut(Hive::LocalMachine).is_some());
+    reg.save().expect("Failed to save registry");
+    assert_eq!(std::fs::metadata(pfx.join("system.reg")).unwrap().modified().unwrap(), written);
+
+    let reg = prefix.registry();
+    assert_eq!(reg.get_value("HKCU\\Software\\Wine\\DllOverrides", "dxgi").and_then(RegValue::as_str), Some("native"));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn prefix_expand_env() {
+    // Variables are read from the registry of the prefix, and can contain variables themselves
+    //
+    // This test requires a writable temp directory
+    use crate::linux::ProtonPrefix;
+
+    let pfx = TestDir::new("prefix-env");
+    create_prefix(&pfx, &[], concat!(
+        "WINE REGISTRY Version 2\n",
+        ";; All keys relative to \\\\User\\\\S-1-5-21-0-0-0-1000\n",
+        "\n#arch=win64\n",
+        "\n[Environment] 1700000000\n",
+        "\"TEMP\"=str(2):\"%USERPROFILE%\\\\AppData\\\\Local\\\\Temp\"\n",
+        "\n[Volatile Environment] 1700000000\n",
+        "\"APPDATA\"=\"C:\\\\users\\\\steamuser\\\\AppData\\\\Roaming\"\n",
+        "\"USERPROFILE\"=\"C:\\\\users\\\\steamuser\"\n"
+    ));
+    std::fs::write(pfx.join("system.reg"), concat!(
+        "WINE REGISTRY Version 2\n",
+        ";; All keys relative to \\\\Machine\n",
+        "\n#arch=win64\n",
+        "\n[Software\\\\Microsoft\\\\Windows\\\\CurrentVersion] 1700000000\n",
+        "\"ProgramFilesDir\"=\"C:\\\\Program Files\"\n",
+        "\n[System\\\\CurrentControlSet\\\\Control\\\\Session Manager\\\\Environment] 1700000000\n",
+        "\"PUBLIC\"=\"C:\\\\users\\\\Public\"\n"
+    )).unwrap();
+
+    let prefix = ProtonPrefix::from_path(pfx.to_path_buf()).expect("Failed to open prefix");
+    assert_eq!(prefix.expand_env("%temp%\\x.log"), "C:\\users\\steamuser\\AppData\\Local\\Temp\\x.log");
+    assert_eq!(prefix.expand_env("%ProgramFiles%\\Game"), "C:\\Program Files\\Game");
+    assert_eq!(prefix.expand_env("%SystemDrive%%PUBLIC%"), "C:C:\\users\\Public");
+    assert_eq!(prefix.expand_env("100% %UNKNOWN% %APPDATA%"), "100% %UNKNOWN% C:\\users\\steamuser\\AppData\\Roaming");
+    assert_eq!(prefix.registry().expand_env_or("%GAME%\\%APPDATA%", |name| (name == "GAME").then(|| "D:".to_string())), "D:\\C:\\users\\steamuser\\AppData\\Roaming");
+
+    let mut expected = pfx.to_path_buf();
+    expected.push("dosdevices/c:/users/steamuser/AppData/Roaming/Game");
+    assert_eq!(prefix.parse_windows_path("%APPDATA%\\Game"), expected);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn prefix_to_windows_path() {
+    // Paths within the prefix map to C:, the rest of the system to Z:
+    //
+    // This test requires a writable temp directory
+    use crate::linux::ProtonPrefix;
+
+    let pfx = TestDir::new("prefix-reverse");
+    create_prefix(&pfx, &["users/steamuser"], "WINE REGISTRY Version 2\n");
+    std::os::unix::fs::symlink("/", pfx.join("dosdevices/z:")).unwrap();
+
+    let prefix = ProtonPrefix::from_path(pfx.to_path_buf()).expect("Failed to open prefix");
+    assert_eq!(prefix.to_windows_path(&pfx.join("drive_c/users/steamuser/My Mods/mod.pak")).as_deref(), Some("C:\\users\\steamuser\\My Mods\\mod.pak"));
+    assert_eq!(prefix.to_windows_path(&pfx.join("dosdevices/c:")).as_deref(), Some("C:\\"));
+    assert_eq!(prefix.to_windows_path(std::path::Path::new("/usr/share")).as_deref(), Some("Z:\\usr\\share"));
+
+    // Round trip
+    let path = prefix.parse_windows_path("C:\\users\\steamuser");
+    assert_eq!(prefix.to_windows_path(&path).as_deref(), Some("C:\\users\\steamuser"));
+
+    // Characters windows does not allow in names are mapped to U+F000 + the character, like wine does
+    let file = pfx.join("drive_c/users/steamuser/a:b?\\c.sav");
+    std::fs::write(&file, "").unwrap();
+    let path = prefix.to_windows_path(&file);
+    assert_eq!(path.as_deref(), Some("C:\\users\\steamuser\\a\u{f03a}b\u{f03f}\u{f05c}c.sav"));
+    assert_eq!(prefix.resolve_windows_path(&path.unwrap()).map(|path| path.canonicalize().unwrap()), Ok(file.canonicalize().unwrap()));
+
+    // Names that are not valid UTF-8 have no windows path
+    use std::os::unix::ffi::OsStrExt;
+    let file = pfx.join("drive_c").join(std::ffi::OsStr::from_bytes(b"inv\xffalid"));
+    assert_eq!(prefix.to_windows_path(&file), None);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn prefix_drives() {
+    // Drive types come from the link targets and HKLM\Software\Wine\Drives,
+    // drives can be added and removed again
+    //
+    // This test requires a writable temp directory
+    use crate::linux::{DriveType, ProtonPrefix};
+
+    let pfx = TestDir::new("prefix-drives");
+    create_prefix(&pfx, &[], "WINE REGISTRY Version 2\n");
+    std::fs::create_dir_all(pfx.join("mods")).unwrap();
+    std::fs::write(pfx.join("system.reg"), concat!(
+        "WINE REGISTRY Version 2\n",
+        ";; All keys relative to \\\\Machine\n",
+        "\n#arch=win64\n",
+        "\n[Software\\\\Wine\\\\Drives] 1700000000\n",
+        "\"d:\"=\"cdrom\"\n"
+    )).unwrap();
+    for (name, target) in [("d:", "/tmp"), ("z:", "/"), ("c::", "/dev/null"), ("com1", "/dev/ttyS0")] {
+        std::os::unix::fs::symlink(target, pfx.join("dosdevices").join(name)).unwrap();
+    }
+
+    let prefix = ProtonPrefix::from_path(pfx.to_path_buf()).expect("Failed to open prefix");
+    let drives: Vec<(char, DriveType)> = prefix.drives().iter().map(|drive| (drive.letter, drive.kind)).collect();
+    assert_eq!(drives, vec![('c', DriveType::Prefix), ('d', DriveType::CdRom), ('z', DriveType::Root)]);
+
+    let drive = prefix.add_drive('M', &pfx.join("mods"), Some(DriveType::Network)).expect("Failed to add drive");
+    assert_eq!(drive.letter, 'm');
+    assert!(matches!(prefix.add_drive('m', &pfx.join("mods"), None), Err(crate::ProtonFinderError::DriveInUse('m'))), "Drive was added twice");
+    assert!(matches!(prefix.add_drive('1', &pfx.join("mods"), None), Err(crate::ProtonFinderError::InvalidDriveLetter('1'))));
+    assert!(matches!(prefix.add_drive('r', std::path::Path::new("/"), Some(DriveType::Root)), Err(crate::ProtonFinderError::InvalidDriveTarget(_))), "Synthetic drive type was accepted");
+    assert!(pfx.join("dosdevices/r:").symlink_metadata().is_err());
+    assert_eq!(prefix.drives().iter().find(|drive| drive.letter == 'm').map(|drive| drive.kind), Some(DriveType::Network));
+    assert_eq!(prefix.to_windows_path(&pfx.join("mods/a.pak")).as_deref(), Some("M:\\a.pak"));
+
+    assert!(matches!(prefix.remove_drive('c'), Err(crate::ProtonFinderError::DriveNotRemovable('c'))), "C: was removed");
+    prefix.remove_drive('m').expect("Failed to remove drive");
+    assert!(pfx.join("mods").is_dir(), "Drive target was removed");
+    assert_eq!(prefix.drives().len(), 3);
+    assert!(prefix.registry().get_value("HKLM\\Software\\Wine\\Drives", "m:").is_none());
+
+    // Without a system.reg the type can not be noted, so the link is removed again
+    std::fs::remove_file(pfx.join("system.reg")).unwrap();
+    assert!(matches!(prefix.add_drive('n', &pfx.join("mods"), Some(DriveType::Network)), Err(crate::ProtonFinderError::RegistryKeyMissing(_))));
+    assert!(pfx.join("dosdevices/n:").symlink_metadata().is_err(), "Link of the failed drive was kept");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn prefix_resolve_ignore_case() {
+    // Paths are matched case insensitive, missing paths return the closest existing folder
+    //
+    // This test requires a writable temp directory
+    use crate::linux::ProtonPrefix;
+
+    let pfx = TestDir::new("prefix-case");
+    create_prefix(&pfx, &["users/steamuser/documents/My Games/Foo", "users/steamuser/documents/Überspiel"], "WINE REGISTRY Version 2\n");
+    std::fs::write(pfx.join("drive_c/users/steamuser/documents/My Games/Foo/config.ini"), "").unwrap();
+
+    let prefix = ProtonPrefix::from_path(pfx.to_path_buf()).expect("Failed to open prefix");
+    let c = pfx.join("dosdevices/c:");
+    assert_eq!(prefix.resolve_windows_path("C:\\Users\\steamuser\\Documents\\MY GAMES\\foo\\Config.ini"), Ok(c.join("users/steamuser/documents/My Games/Foo/config.ini")));
+    assert_eq!(prefix.resolve_windows_path("c:/users/steamuser/Documents/My Games/Bar/Config.ini"), Err(c.join("users/steamuser/documents/My Games")));
+    assert_eq!(prefix.resolve_windows_path("C:\\users\\..\\..\\USERS"), Ok(c.join("users")));
+    assert_eq!(prefix.resolve_windows_path("Q:\\"), Err(pfx.join("dosdevices")));
+
+    // Not only ascii is matched ignoring case
+    assert_eq!(prefix.resolve_windows_path("C:\\users\\steamuser\\Documents\\ÜBERSPIEL"), Ok(c.join("users/steamuser/documents/Überspiel")));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn prefix_known_folder() {
+    // Known folders are read from the Shell Folders, or their default location
+    //
+    // This test requires a writable temp directory
+    use crate::{linux::ProtonPrefix, KnownFolder};
+
+    let pfx = TestDir::new("prefix-known");
+    let folders = ["users/steamuser/Saved Games", "users/steamuser/Documents", "ProgramData", "windows/Fonts", "windows/syswow64"];
+    create_prefix(&pfx, &folders, concat!(
+        "WINE REGISTRY Version 2\n",
+        "\n#arch=win64\n",
+        "\n[Software\\\\Microsoft\\\\Windows\\\\CurrentVersion\\\\Explorer\\\\Shell Folders] 1700000000\n",
+        "\"Personal\"=\"C:\\\\users\\\\steamuser\\\\Documents\"\n",
+        "\n[Volatile Environment] 1700000000\n",
+        "\"USERPROFILE\"=\"C:\\\\users\\\\steamuser\"\n"
+    ));
+    std::fs::write(pfx.join("system.reg"), concat!(
+        "WINE REGISTRY Version 2\n",
+        "\n#arch=win64\n",
+        "\n[Software\\\\Microsoft\\\\Windows\\\\CurrentVersion\\\\Explorer\\\\Shell Folders] 1700000000\n",
+        "\"Common AppData\"=\"C:\\\\ProgramData\"\n",
+        "\n[System\\\\CurrentControlSet\\\\Control\\\\Session Manager\\\\Environment] 1700000000\n",
+        "\"windir\"=\"C:\\\\windows\"\n"
+    )).unwrap();
+
+    let prefix = ProtonPrefix::from_path(pfx.to_path_buf()).expect("Failed to open prefix");
+    let c = pfx.join("drive_c").canonicalize().unwrap();
+    assert_eq!(prefix.known_folder(KnownFolder::Documents), Some(c.join("users/steamuser/Documents")));
+    assert_eq!(prefix.known_folder(KnownFolder::SavedGames), Some(c.join("users/steamuser/Saved Games")));
+    assert_eq!(prefix.known_folder(KnownFolder::ProgramData), Some(c.join("ProgramData")));
+    assert_eq!(prefix.known_folder(KnownFolder::Fonts), Some(c.join("windows/Fonts")));
+    assert_eq!(prefix.known_folder(KnownFolder::SystemX86), Some(c.join("windows/syswow64")));
+    assert_eq!(prefix.known_folder(KnownFolder::Music), None);
+    assert_eq!(prefix.known_folder(KnownFolder::ControlPanelFolder), None);
+
+    assert_eq!(KnownFolder::from_guid("4c5c32ff-bb9d-43b0-b5b4-2d72e54eaaa4"), Some(KnownFolder::SavedGames));
+    assert!(KnownFolder::ALL.iter().all(|folder| KnownFolder::from_guid(folder.guid()) == Some(*folder)), "Duplicate or malformed guid");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn prefix_folder_fallback() {
+    // Without Shell Folders the User Shell Folders and then wine's layout are used,
+    // missing folders are only created with create_missing
+    //
+    // This test requires a writable temp directory
+    use crate::{linux::ProtonPrefix, KnownFolder};
+
+    let pfx = TestDir::new("prefix-fallback");
+    let folders = ["users/steamuser/AppData/Roaming", "users/steamuser/My Docs", "users/steamuser/100% Music"];
+    create_prefix(&pfx, &folders, concat!(
+        "WINE REGISTRY Version 2\n",
+        "\n[Software\\\\Microsoft\\\\Windows\\\\CurrentVersion\\\\Explorer\\\\User Shell Folders] 1700000000\n",
+        "\"My Music\"=str(2):\"%USERPROFILE%\\\\100% Music\"\n",
+        "\"My Pictures\"=str(2):\"%UNKNOWN%\\\\Pictures\"\n",
+        "\"Personal\"=str(2):\"%USERPROFILE%\\\\My Docs\"\n",
+        "\n[Volatile Environment] 1700000000\n",
+        "\"USERPROFILE\"=\"C:\\\\users\\\\steamuser\"\n",
+        "\"APPDATA\"=dword:00000001\n"
+    ));
+
+    let prefix = ProtonPrefix::from_path(pfx.to_path_buf()).expect("Failed to open prefix");
+    let user = pfx.join("drive_c/users/steamuser").canonicalize().unwrap();
+    assert_eq!(prefix.documents_dir(), Some(user.join("My Docs")));
+    assert_eq!(prefix.music_dir(), Some(user.join("100% Music")), "A literal % is not a variable");
+    assert_eq!(prefix.appdata_roaming(), Some(user.join("AppData/Roaming")), "Variable that is not a string did not fall back");
+    assert_eq!(prefix.known_folder(KnownFolder::SavedGames), None);
+    assert_eq!(prefix.temp_dir(), None);
+
+    let prefix = prefix.create_missing(true);
+    assert_eq!(prefix.known_folder(KnownFolder::SavedGames), Some(user.join("Saved Games")));
+    assert_eq!(prefix.picture_dir(), Some(user.join("Pictures")), "Path with an unknown variable was not skipped");
+    assert_eq!(prefix.temp_dir(), Some(user.join("AppData/Local/Temp")));
+    assert!(user.join("AppData/Local/Temp").is_dir());
+    assert_eq!(prefix.known_folder(KnownFolder::ControlPanelFolder), None);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+pub fn steam_root_users() {
+    // Users are read from loginusers.vdf, the most recent one first
+    //
+    // This test requires a writable temp directory
+    let root = TestDir::new("steam-users");
+    std::fs::create_dir_all(root.join("ubuntu12_32")).unwrap();
+    std::fs::create_dir_all(root.join("steamapps")).unwrap();
+    std::fs::create_dir_all(root.join("config")).unwrap();
+    std::fs::create_dir_all(root.join("userdata/22202/2420510/remote")).unwrap();
+    std::fs::create_dir_all(root.join("userdata/22202/760/remote/2420510/screenshots")).unwrap();
+    std::fs::create_dir_all(root.join("userdata/22202/config")).unwrap();
+    std::fs::write(root.join("userdata/22202/config/localconfig.vdf"), "\"UserLocalConfigStore\"\n{\n\t\"streaming_v2\"\n\t{\n\t\t\"EnableStreaming\"\t\t\"0\"\n\t}\n}\n").unwrap();
+    std::fs::write(root.join("config/loginusers.vdf"), concat!(
+        "\"users\"\n{\n",
+        "\t\"76561197960265729\"\n\t{\n\t\t\"AccountName\"\t\t\"old\"\n\t\t\"PersonaName\"\t\t\"Old\"\n\t\t\"MostRecent\"\t\t\"0\"\n\t\t\"Timestamp\"\t\t\"1600000000\"\n\t}\n",
+        "\t\"76561197960287930\"\n\t{\n\t\t\"AccountName\"\t\t\"gaben\"\n\t\t\"PersonaName\"\t\t\"Rabscuttle\"\n\t\t\"mostrecent\"\t\t\"1\"\n\t\t\"Timestamp\"\t\t\"1700000000\"\n\t}\n",
+        "}\n"
+    )).unwrap();
+
+    let steam = crate::linux::steam_root_from(root.to_path_buf()).expect("Temp steam root not valid");
+    let users = steam.users();
+    assert_eq!(users.len(), 2);
+    assert_eq!(users[0].steam_id, 76561197960287930);
+    assert_eq!(users[0].account_id, 22202);
+    assert_eq!(users[0].account_name, "gaben");
+    assert_eq!(users[0].persona_name, "Rabscuttle");
+    assert!(users[0].most_recent);
+    assert_eq!(users[0].timestamp, 1700000000);
+    assert_eq!(users[1].account_id, 1);
+    assert!(!users[1].most_recent);
+
+    assert_eq!(users[0].userdata_dir(), Some(root.join("userdata/22202")));
+    assert_eq!(users[0].app_remote_dir(2420510), Some(root.join("userdata/22202/2420510/remote")));
+    assert_eq!(users[0].screenshots_dir(2420510), Some(root.join("userdata/22202/760/remote/2420510/screenshots")));
+    assert_eq!(users[0].app_remote_dir(1), None);
+    assert!(users[0].read_local_config_vdf_file().is_some_and(|vdf| vdf.contains_key("streaming_v2")));
+    assert_eq!(users[1].userdata_dir(), None);
+}
+
+#[cfg(target_os = "linux")]
+#[allow(deprecated)]
 fn find_prefix_helper(game_id: u32) -> crate::linux::ProtonPrefix {
     let prefix = match crate::linux::find_prefix(game_id) {
         Ok(res) => res,
@@ -82,6 +1155,7 @@ fn find_prefix_helper(game_id: u32) -> crate::linux::ProtonPrefix {
 
 #[cfg(target_os = "linux")]
 #[test]
+#[allow(deprecated)]
 pub fn find_holocure_prefix() {
     // We test if we can find the prefix for the game HoloCure
     //
@@ -92,6 +1166,17 @@ pub fn find_holocure_prefix() {
     }.is_some(), "Unable to find HoloCure Prefix (2420510)");
 }
 
+#[cfg(target_os = "linux")]
+#[test]
+pub fn locate_holocure_prefix() {
+    // We test if we can find the prefix for the game HoloCure, through locate_prefix
+    //
+    // This test fails if steam and HoloCure have not been installed and initialized
+    let res = crate::linux::locate_prefix(2420510);
+    assert!(res.result.is_ok(), "Unable to find HoloCure Prefix (2420510): {:?}", res.result.as_ref().err());
+    assert_eq!(res.ok().map(|prefix| prefix.get_game_id()), Some(2420510));
+}
+
 
 #[cfg(target_os = "linux")]
 #[test]
@@ -128,6 +1213,7 @@ pub fn find_holocure_appdata_roaming() {
 }
 
 #[test]
+#[allow(deprecated)]
 pub fn universal_find_holocure_gamedrive() {
     // This test can never fail under windows
     // But it can fail under Linux if HoloCure is not installed (like for the tests above)
@@ -140,6 +1226,29 @@ pub fn universal_find_holocure_gamedrive() {
     assert!(game_drive.is_some(), "Unable to find windows enviroment for game HoloCure (2420510)");
 }
 
+#[test]
+pub fn universal_game_drive_error() {
+    // We test that a game that can not be installed is reported with the right error
+    // (under Windows no check is done, so this always succeeds)
+
+    let res = crate::locate_game_drive(0);
+    #[cfg(target_os = "linux")]
+    assert!(matches!(res.result, Err(crate::ProtonFinderError::PrefixNotFound(0)) | Err(crate::ProtonFinderError::NoSteamRoot)), "Expected PrefixNotFound, got {:?}", res.result.err());
+    #[cfg(target_os = "windows")]
+    assert!(res.result.is_ok());
+}
+
+#[test]
+pub fn universal_locate_holocure_gamedrive() {
+    // This test can never fail under windows
+    // But it can fail under Linux if HoloCure is not installed (like for the tests above)
+
+    let game_drive = crate::locate_game_drive(2420510).ok();
+
+    assert!(game_drive.is_some(), "Unable to find windows enviroment for game HoloCure (2420510)");
+}
+
+#[allow(deprecated)]
 fn get_game_drive_helper(game_id: u32) -> crate::GameDrive {
     let prefix = match crate::get_game_drive(game_id) {
         Ok(res) => res,
@@ -160,6 +1269,16 @@ pub fn universal_find_holocure_c_drive() {
     assert!(path.is_dir(), "C drive within enviroment for HoloCure not found: {}", path.to_str().unwrap());
 }
 
+#[test]
+pub fn universal_find_holocure_install_dir() {
+    // This test can fail under Windows and Linux if HoloCure is not installed
+
+    let path = get_game_drive_helper(2420510).install_dir();
+    assert!(path.is_some(), "Install folder of HoloCure not found: None returned");
+    let path = path.unwrap();
+    assert!(path.is_dir(), "Install folder of HoloCure not found: {}", path.to_str().unwrap());
+}
+
 #[test]
 pub fn universal_find_holocure_user_folder() {
     // This test can fail under Windows (but incredibly unlikely)
@@ -198,12 +1317,12 @@ pub fn universal_find_holocure_version_ini() {
 }
 
 // fn example() {
-//     let res = crate::get_game_drive(2420510).map_or_else(|e| {
-//         println!("Steam Dir provided is not correctly formated, ignored...");
-//         e
-//     }, |k| k);
+//     let res = crate::locate_game_drive(2420510);
+//     for warn in res.warnings.iter() {
+//         println!("{}, ignored...", warn);
+//     }
 //
-//     if let Some(game_drive) = res {
+//     if let Ok(game_drive) = res.result {
 //         if let Some(mut path) = game_drive.config_local_dir() {
 //             path.push("HoloCure");
 //             path.push("settings.json");
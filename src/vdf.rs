@@ -0,0 +1,708 @@
+use std::{ffi::OsString, fmt::Display, fs::{self, OpenOptions}, io::{self, Write}, iter::Peekable, path::Path, str::Chars, sync::atomic::{AtomicUsize, Ordering}};
+
+use crate::ProtonFinderError;
+
+/// Reader for the binary KeyValues format, used by appinfo.vdf, packageinfo.vdf and shortcuts.vdf
+pub mod binary;
+
+#[cfg(feature = "serde")]
+mod de;
+#[cfg(feature = "serde")]
+mod ser;
+#[cfg(feature = "serde")]
+pub use de::{from_file, from_str, from_struct, Error as SerdeError};
+#[cfg(feature = "serde")]
+pub use ser::{to_string, to_struct};
+
+/// How deep `#include`/`#base` directives are followed, this stops include loops
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// Conditionals (like `[$WIN32]`) that evaluate to true on this platform
+#[cfg(target_os = "windows")]
+const TRUE_CONDITIONS: [&str; 2] = ["$WIN32", "$WINDOWS"];
+#[cfg(not(target_os = "windows"))]
+const TRUE_CONDITIONS: [&str; 2] = ["$LINUX", "$POSIX"];
+
+/// Parses a vdf file at the given location
+///
+/// This handles the complete text KeyValues format, so quoted and unquoted tokens, escape
+/// sequences, `//` comments, brackets on the same line as the key and conditionals (like
+/// `[$WIN32]`, entries with a false condition are skipped).
+/// `#include` and `#base` directives are resolved relative to the folder of the file.
+pub fn parse_vdf_file(file_path: &Path) -> Option<VdfStruct> {
+    try_parse_vdf_file(file_path).ok()
+}
+
+/// Parses a vdf file at the given location, same as `parse_vdf_file`,
+/// but reports why it failed (with the line for syntax errors)
+pub fn try_parse_vdf_file(file_path: &Path) -> Result<VdfStruct, ProtonFinderError> {
+    parse_file(file_path, 0)
+}
+
+/// Parses vdf text, same as `parse_vdf_file`.
+///
+/// As there is no file to resolve them from, `#include` and `#base` directives are ignored.
+pub fn parse_vdf_str(text: &str) -> Option<VdfStruct> {
+    try_parse_vdf_str(text).ok()
+}
+
+/// Parses vdf text, same as `parse_vdf_str`, but reports why it failed
+pub fn try_parse_vdf_str(text: &str) -> Result<VdfStruct, ProtonFinderError> {
+    let mut tokens = Tokenizer::new(text);
+    parse_struct(&mut tokens, true, &mut Vec::new()).map_err(|err| err.into_error(None))
+}
+
+/// Writes the struct as text vdf to the given location
+///
+/// The output is formated the same way steam does (quoted tokens, tab indentation and escaping).
+/// A file steam wrote itself is written back byte for byte if unmodified, other files only keep
+/// their values: comments, unquoted tokens and the formating are not preserved, entries behind a
+/// false conditional (like `[$WIN32]` under Linux) are lost, and backslashes that did not start an
+/// escape sequence (like in `"C:\Games"`) are written escaped (`"C:\\Games"`), which steam reads
+/// as the same value.
+/// To not leave a broken file behind on failure, the data is first written into a temporary file
+/// in the same folder, which then replaces the original file.
+pub fn write_vdf_file(file_path: &Path, vdf: &VdfStruct) -> io::Result<()> {
+    write_file_atomic(file_path, vdf.to_vdf_string().as_bytes())
+}
+
+/// Counts up the temporary files, so concurrent writes within this process don't collide
+static TMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Replaces the file with the data, without leaving a half written file behind on failure.
+///
+/// The data goes into a uniquely named temporary file next to the original, which gets the
+/// permissions of the original and is then renamed over it
+pub(crate) fn write_file_atomic(file_path: &Path, data: &[u8]) -> io::Result<()> {
+    let mut tmp_name = OsString::from(".");
+    tmp_name.push(file_path.file_name().ok_or(io::ErrorKind::InvalidInput)?);
+    tmp_name.push(format!(".{}.{}.tmp", std::process::id(), TMP_COUNTER.fetch_add(1, Ordering::Relaxed)));
+    let tmp_path = file_path.with_file_name(tmp_name);
+
+    let res = (|| {
+        let mut file = OpenOptions::new().write(true).create_new(true).open(&tmp_path)?;
+        file.write_all(data)?;
+        if let Ok(meta) = fs::metadata(file_path) {
+            file.set_permissions(meta.permissions())?;
+        }
+        file.sync_all()?;
+        fs::rename(&tmp_path, file_path)
+    })();
+
+    if res.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    res
+}
+
+fn parse_file(file_path: &Path, depth: usize) -> Result<VdfStruct, ProtonFinderError> {
+    let bytes = fs::read(file_path)?;
+    let text = String::from_utf8_lossy(&bytes);
+    let text = text.strip_prefix('\u{feff}').unwrap_or(&text);
+
+    let mut tokens = Tokenizer::new(text);
+    let mut directives = Vec::new();
+    let mut obj = parse_struct(&mut tokens, true, &mut directives).map_err(|err| err.into_error(Some(file_path)))?;
+
+    if depth < MAX_INCLUDE_DEPTH {
+        for dir in directives {
+            let mut path = file_path.to_path_buf();
+            path.pop();
+            path.push(dir.file);
+
+            // Like steam we don't fail when the referenced file is missing
+            if let Ok(other) = parse_file(&path, depth + 1) {
+                if dir.base {
+                    obj.merge_base(other);
+                } else {
+                    obj.append(other);
+                }
+            }
+        }
+    }
+
+    Ok(obj)
+}
+
+/// Parses structs recursively, directives are only collected when this is the root
+fn parse_struct(tokens: &mut Tokenizer, root: bool, directives: &mut Vec<Directive>) -> Result<VdfStruct, ParseError> {
+    let mut obj = VdfStruct::new();
+
+    loop {
+        let key = match tokens.next()? {
+            None if root => return Ok(obj),
+            None => return Err(tokens.error("unexpected end of file, missing '}'")),
+            Some(Token::Close) if !root => return Ok(obj),
+            Some(Token::Str { text, quoted }) => {
+                if root && !quoted && (text.eq_ignore_ascii_case("#include") || text.eq_ignore_ascii_case("#base")) {
+                    match tokens.next()? {
+                        Some(Token::Str { text: file, .. }) => {
+                            directives.push(Directive { base: text.eq_ignore_ascii_case("#base"), file });
+                            continue;
+                        },
+                        _ => return Err(tokens.error("expected a file name after the directive"))
+                    }
+                }
+
+                text
+            },
+            Some(_) => return Err(tokens.error("expected a key"))
+        };
+
+        let mut next = tokens.next()?;
+        let mut condition = true;
+        if let Some(Token::Conditional(cond)) = next {
+            condition = evaluate_conditional(&cond);
+            next = tokens.next()?;
+        }
+
+        let value = match next {
+            Some(Token::Open) => VdfValue::Complex(parse_struct(tokens, false, directives)?),
+            Some(Token::Str { text, .. }) => {
+                if matches!(tokens.peek()?, Some(Token::Conditional(_))) {
+                    if let Some(Token::Conditional(cond)) = tokens.next()? {
+                        condition = condition && evaluate_conditional(&cond);
+                    }
+                }
+
+                VdfValue::Simple(text)
+            },
+            None => return Err(tokens.error("unexpected end of file, key is missing a value")),
+            Some(_) => return Err(tokens.error("expected a value or '{'"))
+        };
+
+        if condition {
+            obj.push(key, value);
+        }
+    }
+}
+
+/// Evaluates conditionals like `$WIN32`, `!$X360` or `$WIN32 || $OSX`.
+/// `&&` binds stronger then `||`, same as in C
+fn evaluate_conditional(cond: &str) -> bool {
+    cond.split("||").any(|and_part| {
+        and_part.split("&&").all(|term| {
+            let term = term.trim();
+            if let Some(negated) = term.strip_prefix('!') {
+                !TRUE_CONDITIONS.iter().any(|c| c.eq_ignore_ascii_case(negated.trim()))
+            } else {
+                TRUE_CONDITIONS.iter().any(|c| c.eq_ignore_ascii_case(term))
+            }
+        })
+    })
+}
+
+struct Directive {
+    base: bool,
+    file: String
+}
+
+#[derive(Debug)]
+struct ParseError {
+    line: usize,
+    reason: &'static str
+}
+
+impl ParseError {
+    fn into_error(self, path: Option<&Path>) -> ProtonFinderError {
+        ProtonFinderError::Parse { path: path.map(Path::to_path_buf), line: self.line, reason: self.reason.to_string() }
+    }
+}
+
+enum Token {
+    Str { text: String, quoted: bool },
+    Open,
+    Close,
+    Conditional(String)
+}
+
+/// Splits the text into tokens, while skipping whitespace and comments
+struct Tokenizer<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    peeked: Option<Option<Token>>
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(text: &'a str) -> Self {
+        Tokenizer { chars: text.chars().peekable(), line: 1, peeked: None }
+    }
+
+    fn error(&self, reason: &'static str) -> ParseError {
+        ParseError { line: self.line, reason }
+    }
+
+    fn peek(&mut self) -> Result<&Option<Token>, ParseError> {
+        if self.peeked.is_none() {
+            let tok = self.read_token()?;
+            self.peeked = Some(tok);
+        }
+
+        Ok(self.peeked.as_ref().expect("was just set"))
+    }
+
+    fn next(&mut self) -> Result<Option<Token>, ParseError> {
+        match self.peeked.take() {
+            Some(tok) => Ok(tok),
+            None => self.read_token()
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        if c == Some('\n') {
+            self.line += 1;
+        }
+        c
+    }
+
+    fn read_token(&mut self) -> Result<Option<Token>, ParseError> {
+        // Skipping whitespace and comments
+        loop {
+            match self.chars.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.bump();
+                },
+                Some('/') => {
+                    let mut ahead = self.chars.clone();
+                    ahead.next();
+                    if ahead.peek() != Some(&'/') {
+                        break;
+                    }
+
+                    while !matches!(self.chars.peek(), None | Some('\n')) {
+                        self.bump();
+                    }
+                },
+                _ => break
+            }
+        }
+
+        let c = match self.bump() {
+            Some(c) => c,
+            None => return Ok(None)
+        };
+
+        match c {
+            '{' => Ok(Some(Token::Open)),
+            '}' => Ok(Some(Token::Close)),
+            '"' => {
+                let mut text = String::new();
+                loop {
+                    match self.bump() {
+                        None => return Err(self.error("unterminated string")),
+                        Some('"') => break,
+                        Some('\\') => match self.bump() {
+                            Some('n') => text.push('\n'),
+                            Some('t') => text.push('\t'),
+                            Some('r') => text.push('\r'),
+                            Some('\\') => text.push('\\'),
+                            Some('"') => text.push('"'),
+                            Some(other) => {
+                                // Unknown escapes are kept as is
+                                text.push('\\');
+                                text.push(other);
+                            },
+                            None => return Err(self.error("unterminated string"))
+                        },
+                        Some(c) => text.push(c)
+                    }
+                }
+
+                Ok(Some(Token::Str { text, quoted: true }))
+            },
+            '[' => {
+                let mut cond = String::new();
+                loop {
+                    match self.bump() {
+                        None | Some('\n') => return Err(self.error("unterminated conditional")),
+                        Some(']') => break,
+                        Some(c) => cond.push(c)
+                    }
+                }
+
+                Ok(Some(Token::Conditional(cond)))
+            },
+            c => {
+                let mut text = String::from(c);
+                while let Some(&c) = self.chars.peek() {
+                    if c.is_whitespace() || matches!(c, '"' | '{' | '}') {
+                        break;
+                    }
+                    text.push(c);
+                    self.chars.next();
+                }
+
+                Ok(Some(Token::Str { text, quoted: false }))
+            }
+        }
+    }
+}
+
+/// Represents a Vdf Complextype with multiple key value pairs, where the value can be further nested structs
+///
+/// The pairs are kept in the order they are in the file, and the same key can appear multiple
+/// times (KeyValues allows this, and some steam files rely on it).
+/// Lookups via `get` are case sensitive, `get_ignore_case` matches how steam looks keys up.
+/// For duplicate keys the lookups return the last pair, same as when the pairs were stored in a HashMap.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VdfStruct {
+    pub pairs: Vec<(String, VdfValue)>
+}
+
+impl VdfStruct {
+    /// Creates an empty struct
+    pub fn new() -> Self {
+        VdfStruct { pairs: Vec::new() }
+    }
+
+    /// Returns the value of the last pair with this key
+    pub fn get(&self, key: &str) -> Option<&VdfValue> {
+        self.pairs.iter().rev().find(|(k,_)| k == key).map(|(_,v)| v)
+    }
+
+    /// Returns the value of the last pair with this key, ignoring the case of the key
+    pub fn get_ignore_case(&self, key: &str) -> Option<&VdfValue> {
+        self.pairs.iter().rev().find(|(k,_)| k.eq_ignore_ascii_case(key)).map(|(_,v)| v)
+    }
+
+    /// Returns a mutable reference to the value of the last pair with this key
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut VdfValue> {
+        self.pairs.iter_mut().rev().find(|(k,_)| k == key).map(|(_,v)| v)
+    }
+
+    /// Returns the values of all pairs with this key, in order
+    pub fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a VdfValue> + 'a {
+        self.pairs.iter().filter(move |(k,_)| k == key).map(|(_,v)| v)
+    }
+
+    /// Returns the values of all pairs with this key (ignoring the case of the key), in order
+    pub fn get_all_ignore_case<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a VdfValue> + 'a {
+        self.pairs.iter().filter(move |(k,_)| k.eq_ignore_ascii_case(key)).map(|(_,v)| v)
+    }
+
+    /// Returns the value at the path, a `/` seperated list of keys of nested structs
+    /// (like `"UserLocalConfigStore/Software/Valve/Steam/apps/2420510/LaunchOptions"`).
+    ///
+    /// Keys are matched ignoring case (same as steam does), for duplicate keys the last is used.
+    pub fn get_path(&self, path: &str) -> Result<&VdfValue, VdfAccessError> {
+        let mut obj = self;
+        let mut walked = String::new();
+        let mut segments = path.split('/').filter(|seg| !seg.is_empty()).peekable();
+
+        while let Some(seg) = segments.next() {
+            if !walked.is_empty() {
+                walked.push('/');
+            }
+            walked.push_str(seg);
+
+            let value = obj.get_ignore_case(seg).ok_or_else(|| VdfAccessError::Missing(walked.clone()))?;
+            if segments.peek().is_none() {
+                return Ok(value);
+            }
+
+            obj = match value {
+                VdfValue::Complex(next) => next,
+                _ => return Err(VdfAccessError::NotAStruct(walked))
+            };
+        }
+
+        Err(VdfAccessError::Missing(walked))
+    }
+
+    /// Returns a mutable reference to the value at the path, same as `get_path`
+    pub fn get_path_mut(&mut self, path: &str) -> Result<&mut VdfValue, VdfAccessError> {
+        let mut obj = self;
+        let mut walked = String::new();
+        let mut segments = path.split('/').filter(|seg| !seg.is_empty()).peekable();
+
+        while let Some(seg) = segments.next() {
+            if !walked.is_empty() {
+                walked.push('/');
+            }
+            walked.push_str(seg);
+
+            let value = obj.pairs.iter_mut().rev()
+                .find(|(k,_)| k.eq_ignore_ascii_case(seg))
+                .map(|(_,v)| v)
+                .ok_or_else(|| VdfAccessError::Missing(walked.clone()))?;
+            if segments.peek().is_none() {
+                return Ok(value);
+            }
+
+            obj = match value {
+                VdfValue::Complex(next) => next,
+                _ => return Err(VdfAccessError::NotAStruct(walked))
+            };
+        }
+
+        Err(VdfAccessError::Missing(walked))
+    }
+
+    /// If a pair with this key exists
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Sets the value of the last pair with this key,
+    /// or appends a new pair if there is none.
+    ///
+    /// Returns the previous value
+    pub fn insert(&mut self, key: impl Into<String>, value: VdfValue) -> Option<VdfValue> {
+        let key = key.into();
+        if let Some(old) = self.get_mut(&key) {
+            Some(std::mem::replace(old, value))
+        } else {
+            self.pairs.push((key, value));
+            None
+        }
+    }
+
+    /// Appends a pair, even if the key already exists
+    pub fn push(&mut self, key: impl Into<String>, value: VdfValue) {
+        self.pairs.push((key.into(), value));
+    }
+
+    /// Removes all pairs with this key, returning the value of the last one
+    pub fn remove(&mut self, key: &str) -> Option<VdfValue> {
+        let mut res = None;
+        let mut index = 0;
+        while index < self.pairs.len() {
+            if self.pairs[index].0 == key {
+                let (_, value) = self.pairs.remove(index);
+                res = Some(value);
+            } else {
+                index += 1;
+            }
+        }
+
+        res
+    }
+
+    /// Iterates over all pairs in order
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &VdfValue)> {
+        self.pairs.iter().map(|(k,v)| (k,v))
+    }
+
+    /// Iterates over all keys in order (duplicates included)
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.pairs.iter().map(|(k,_)| k)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Serializes this struct into text vdf, formated the same way steam writes it's files
+    pub fn to_vdf_string(&self) -> String {
+        let mut out = String::new();
+        self.write_pairs(&mut out, 0);
+        out
+    }
+
+    fn write_pairs(&self, out: &mut String, depth: usize) {
+        for (key, value) in self.pairs.iter() {
+            push_indent(out, depth);
+            push_escaped(out, key);
+
+            match value {
+                VdfValue::Simple(val) => {
+                    out.push_str("\t\t");
+                    push_escaped(out, val);
+                    out.push('\n');
+                },
+                VdfValue::Complex(obj) => {
+                    out.push('\n');
+                    push_indent(out, depth);
+                    out.push_str("{\n");
+                    obj.write_pairs(out, depth + 1);
+                    push_indent(out, depth);
+                    out.push_str("}\n");
+                },
+                // Text vdf has no types, so these are written as their text representation
+                typed => {
+                    out.push_str("\t\t");
+                    push_escaped(out, &typed.to_text().unwrap_or_default());
+                    out.push('\n');
+                }
+            }
+        }
+    }
+
+    /// Appends all pairs of the other struct (`#include`)
+    fn append(&mut self, mut other: VdfStruct) {
+        self.pairs.append(&mut other.pairs);
+    }
+
+    /// Recursively merges the other struct in as a base (`#base`),
+    /// so only filling in what is missing
+    fn merge_base(&mut self, other: VdfStruct) {
+        for (key, value) in other.pairs {
+            match (self.get_mut(&key), value) {
+                (Some(VdfValue::Complex(own)), VdfValue::Complex(base)) => own.merge_base(base),
+                (Some(_), _) => (),
+                (None, value) => self.push(key, value)
+            }
+        }
+    }
+}
+
+fn push_indent(out: &mut String, depth: usize) {
+    for _ in 0..depth {
+        out.push('\t');
+    }
+}
+
+/// Writes the string quoted, escaping the same characters the parser unescapes
+fn push_escaped(out: &mut String, text: &str) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c)
+        }
+    }
+    out.push('"');
+}
+
+/// Represents the value types for vdf:
+/// - Simpletype, which is a String value
+/// - Complextype, which is a struct enclosed in { and }
+///
+/// The remaining types only appear in binary vdf (text vdf has no types),
+/// as further types may be added matches have to include a wildcard arm
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum VdfValue {
+    Complex(VdfStruct),
+    Simple(String),
+    Int32(i32),
+    UInt64(u64),
+    Int64(i64),
+    Float(f32),
+    /// RGBA
+    Color([u8; 4]),
+    Pointer(u32)
+}
+
+impl VdfValue {
+    /// Returns the value as text, the same way it would be written into a text vdf.
+    /// None for Complextypes
+    pub fn to_text(&self) -> Option<String> {
+        match self {
+            VdfValue::Complex(_) => None,
+            VdfValue::Simple(val) => Some(val.clone()),
+            VdfValue::Int32(val) => Some(val.to_string()),
+            VdfValue::UInt64(val) => Some(val.to_string()),
+            VdfValue::Int64(val) => Some(val.to_string()),
+            VdfValue::Float(val) => Some(val.to_string()),
+            VdfValue::Color([r, g, b, a]) => Some(format!("{} {} {} {}", r, g, b, a)),
+            VdfValue::Pointer(val) => Some(val.to_string())
+        }
+    }
+
+    /// Returns the text of a Simpletype
+    pub fn as_str(&self) -> Result<&str, VdfAccessError> {
+        match self {
+            VdfValue::Simple(val) => Ok(val),
+            other => Err(VdfAccessError::WrongType { expected: "string", found: other.type_name() })
+        }
+    }
+
+    /// Returns the value as a number, parsing it from Simpletypes
+    pub fn as_u64(&self) -> Result<u64, VdfAccessError> {
+        let res = match self {
+            VdfValue::Simple(val) => val.trim().parse().ok(),
+            VdfValue::Int32(val) => u64::try_from(*val).ok(),
+            VdfValue::UInt64(val) => Some(*val),
+            VdfValue::Int64(val) => u64::try_from(*val).ok(),
+            VdfValue::Pointer(val) => Some(u64::from(*val)),
+            other => return Err(VdfAccessError::WrongType { expected: "number", found: other.type_name() })
+        };
+
+        res.ok_or_else(|| VdfAccessError::Unparsable { expected: "number", value: self.to_text().unwrap_or_default() })
+    }
+
+    /// Returns the value as a bool, steam writes these as `"1"` and `"0"`
+    /// (`"true"` and `"false"` are accepted too)
+    pub fn as_bool(&self) -> Result<bool, VdfAccessError> {
+        let res = match self {
+            VdfValue::Simple(val) => match val.trim() {
+                "1" => Some(true),
+                "0" => Some(false),
+                t if t.eq_ignore_ascii_case("true") => Some(true),
+                t if t.eq_ignore_ascii_case("false") => Some(false),
+                _ => None
+            },
+            VdfValue::Int32(val) => Some(*val != 0),
+            VdfValue::UInt64(val) => Some(*val != 0),
+            VdfValue::Int64(val) => Some(*val != 0),
+            other => return Err(VdfAccessError::WrongType { expected: "bool", found: other.type_name() })
+        };
+
+        res.ok_or_else(|| VdfAccessError::Unparsable { expected: "bool", value: self.to_text().unwrap_or_default() })
+    }
+
+    /// Returns the struct of a Complextype
+    pub fn as_struct(&self) -> Result<&VdfStruct, VdfAccessError> {
+        match self {
+            VdfValue::Complex(obj) => Ok(obj),
+            other => Err(VdfAccessError::WrongType { expected: "struct", found: other.type_name() })
+        }
+    }
+
+    /// Returns the struct of a Complextype mutably
+    pub fn as_struct_mut(&mut self) -> Result<&mut VdfStruct, VdfAccessError> {
+        match self {
+            VdfValue::Complex(obj) => Ok(obj),
+            other => Err(VdfAccessError::WrongType { expected: "struct", found: other.type_name() })
+        }
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self {
+            VdfValue::Complex(_) => "struct",
+            VdfValue::Simple(_) => "string",
+            VdfValue::Int32(_) => "int32",
+            VdfValue::UInt64(_) => "uint64",
+            VdfValue::Int64(_) => "int64",
+            VdfValue::Float(_) => "float",
+            VdfValue::Color(_) => "color",
+            VdfValue::Pointer(_) => "pointer"
+        }
+    }
+}
+
+/// Error returned when accessing values with `VdfStruct::get_path` and the `VdfValue::as_*` functions
+#[derive(Debug, Clone, PartialEq)]
+pub enum VdfAccessError {
+    /// There is no key at this path
+    Missing(String),
+    /// The path continues, but the value at this path is not a struct
+    NotAStruct(String),
+    /// The value is of a different type then requested
+    WrongType { expected: &'static str, found: &'static str },
+    /// The value is text that could not be parsed into the requested type
+    Unparsable { expected: &'static str, value: String }
+}
+
+impl Display for VdfAccessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VdfAccessError::Missing(path) => write!(f, "no key at \"{}\"", path),
+            VdfAccessError::NotAStruct(path) => write!(f, "value at \"{}\" is not a struct", path),
+            VdfAccessError::WrongType { expected, found } => write!(f, "expected a {}, found a {}", expected, found),
+            VdfAccessError::Unparsable { expected, value } => write!(f, "unable to parse a {} from \"{}\"", expected, value)
+        }
+    }
+}
+
+impl std::error::Error for VdfAccessError {}
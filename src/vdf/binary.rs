@@ -0,0 +1,358 @@
+use std::{fs, path::Path};
+
+use super::{VdfStruct, VdfValue};
+
+// Type bytes preceding each binary key value pair
+const TYPE_SECTION: u8 = 0x00;
+const TYPE_STRING: u8 = 0x01;
+const TYPE_INT32: u8 = 0x02;
+const TYPE_FLOAT: u8 = 0x03;
+const TYPE_POINTER: u8 = 0x04;
+const TYPE_WIDE_STRING: u8 = 0x05;
+const TYPE_COLOR: u8 = 0x06;
+const TYPE_UINT64: u8 = 0x07;
+const TYPE_END: u8 = 0x08;
+const TYPE_INT64: u8 = 0x0A;
+const TYPE_END_ALT: u8 = 0x0B;
+
+// Magic numbers at the start of appinfo.vdf, the last byte is the version
+const APPINFO_MAGIC_27: u32 = 0x07564427;
+const APPINFO_MAGIC_28: u32 = 0x07564428;
+const APPINFO_MAGIC_29: u32 = 0x07564429;
+
+// Magic numbers at the start of packageinfo.vdf
+const PACKAGEINFO_MAGIC_27: u32 = 0x06565527;
+const PACKAGEINFO_MAGIC_28: u32 = 0x06565528;
+
+/// Parses a binary vdf file at the given location, like `userdata/<user>/config/shortcuts.vdf`
+///
+/// For `appinfo.vdf` and `packageinfo.vdf` use `read_appinfo_file` and `read_packageinfo_file`,
+/// as these contain a header in front of each entry.
+pub fn parse_binary_vdf_file(file_path: &Path) -> Option<VdfStruct> {
+    let data = fs::read(file_path).ok()?;
+    parse_binary_vdf(&data)
+}
+
+/// Parses binary vdf data, same as `parse_binary_vdf_file`
+pub fn parse_binary_vdf(data: &[u8]) -> Option<VdfStruct> {
+    let mut reader = Reader { data, pos: 0 };
+    parse_section(&mut reader, None, true)
+}
+
+/// The content of the `appcache/appinfo.vdf` file, containing the metadata of every app the
+/// steam client has seen
+#[derive(Debug, Clone)]
+pub struct AppInfo {
+    /// Format version, 27 to 29 are supported (29 introduced the string table)
+    pub version: u8,
+    pub universe: u32,
+    pub apps: Vec<AppInfoEntry>
+}
+
+impl AppInfo {
+    /// Returns the entry for this app
+    pub fn get(&self, app_id: u32) -> Option<&AppInfoEntry> {
+        self.apps.iter().find(|entry| entry.app_id == app_id)
+    }
+}
+
+/// A single app within the appinfo.vdf
+#[derive(Debug, Clone)]
+pub struct AppInfoEntry {
+    pub app_id: u32,
+    pub info_state: u32,
+    pub last_updated: u32,
+    pub pics_token: u64,
+    /// SHA1 of the text representation of the data
+    pub sha1: [u8; 20],
+    pub change_number: u32,
+    /// SHA1 of the binary data, only present from version 28 onwards
+    pub binary_sha1: Option<[u8; 20]>,
+    /// The data itself, this contains a single "appinfo" struct
+    pub data: VdfStruct
+}
+
+/// Reads the complete appinfo.vdf at the given location (usually `<steamroot>/appcache/appinfo.vdf`)
+///
+/// This file easily reaches hundreds of MB, so if you only need one app use `read_appinfo_entry`
+pub fn read_appinfo_file(file_path: &Path) -> Option<AppInfo> {
+    let data = fs::read(file_path).ok()?;
+    let (mut reader, version, universe, strings) = read_appinfo_header(&data)?;
+
+    let mut apps = Vec::new();
+    while let Some(entry) = read_appinfo_entry_header(&mut reader, version)? {
+        apps.push(entry.parse(strings.as_deref())?);
+    }
+
+    Some(AppInfo { version, universe, apps })
+}
+
+/// Reads the entry for a single app from the appinfo.vdf at the given location.
+///
+/// Only the data of this app is parsed, all other entries are skipped.
+/// If you need multiple apps use `read_appinfo_entries`, so the file is only read once
+pub fn read_appinfo_entry(file_path: &Path, app_id: u32) -> Option<AppInfoEntry> {
+    read_appinfo_entries(file_path, &[app_id]).pop()
+}
+
+/// Reads the entries for the given apps from the appinfo.vdf at the given location,
+/// in the order they are in the file. Apps that are missing are left out.
+///
+/// Only the data of these apps is parsed, all other entries are skipped
+pub fn read_appinfo_entries(file_path: &Path, app_ids: &[u32]) -> Vec<AppInfoEntry> {
+    let mut res = Vec::new();
+    let Ok(data) = fs::read(file_path) else {
+        return res;
+    };
+    let Some((mut reader, version, _, strings)) = read_appinfo_header(&data) else {
+        return res;
+    };
+
+    while let Some(Some(entry)) = read_appinfo_entry_header(&mut reader, version) {
+        if app_ids.contains(&entry.app_id) {
+            match entry.parse(strings.as_deref()) {
+                Some(entry) => res.push(entry),
+                None => break
+            }
+
+            if res.len() == app_ids.len() {
+                break;
+            }
+        }
+    }
+
+    res
+}
+
+/// Reads magic, universe and (for version 29) the string table
+fn read_appinfo_header(data: &[u8]) -> Option<(Reader<'_>, u8, u32, Option<Vec<String>>)> {
+    let mut reader = Reader { data, pos: 0 };
+    let magic = reader.u32()?;
+    let universe = reader.u32()?;
+
+    let (version, strings) = match magic {
+        APPINFO_MAGIC_27 => (27, None),
+        APPINFO_MAGIC_28 => (28, None),
+        APPINFO_MAGIC_29 => {
+            let offset = usize::try_from(reader.u64()?).ok()?;
+
+            let mut table = Reader { data, pos: offset };
+            let count = table.u32()?;
+            let mut strings = Vec::with_capacity(count.min(1 << 16) as usize);
+            for _ in 0..count {
+                strings.push(table.cstr()?);
+            }
+
+            (29, Some(strings))
+        },
+        _ => return None
+    };
+
+    Some((reader, version, universe, strings))
+}
+
+/// The entry header, with the unparsed data
+struct RawAppInfoEntry<'a> {
+    app_id: u32,
+    info_state: u32,
+    last_updated: u32,
+    pics_token: u64,
+    sha1: [u8; 20],
+    change_number: u32,
+    binary_sha1: Option<[u8; 20]>,
+    data: Reader<'a>
+}
+
+impl RawAppInfoEntry<'_> {
+    fn parse(mut self, strings: Option<&[String]>) -> Option<AppInfoEntry> {
+        let data = parse_section(&mut self.data, strings, true)?;
+
+        Some(AppInfoEntry {
+            app_id: self.app_id,
+            info_state: self.info_state,
+            last_updated: self.last_updated,
+            pics_token: self.pics_token,
+            sha1: self.sha1,
+            change_number: self.change_number,
+            binary_sha1: self.binary_sha1,
+            data
+        })
+    }
+}
+
+/// Reads the header of the next entry, and moves the reader past it's data.
+/// Some(None) is returned when the end marker (app id 0) was reached
+fn read_appinfo_entry_header<'a>(reader: &mut Reader<'a>, version: u8) -> Option<Option<RawAppInfoEntry<'a>>> {
+    let app_id = reader.u32()?;
+    if app_id == 0 {
+        return Some(None);
+    }
+
+    // size counts all bytes following it
+    let size = reader.u32()? as usize;
+    let end = reader.pos.checked_add(size)?;
+    if end > reader.data.len() {
+        return None;
+    }
+
+    let mut entry_reader = Reader { data: &reader.data[..end], pos: reader.pos };
+    let info_state = entry_reader.u32()?;
+    let last_updated = entry_reader.u32()?;
+    let pics_token = entry_reader.u64()?;
+    let sha1 = entry_reader.sha1()?;
+    let change_number = entry_reader.u32()?;
+    let binary_sha1 = if version >= 28 {
+        Some(entry_reader.sha1()?)
+    } else {
+        None
+    };
+
+    reader.pos = end;
+    Some(Some(RawAppInfoEntry { app_id, info_state, last_updated, pics_token, sha1, change_number, binary_sha1, data: entry_reader }))
+}
+
+/// The content of the `appcache/packageinfo.vdf` file, containing the metadata of licenses/packages
+#[derive(Debug, Clone)]
+pub struct PackageInfo {
+    /// Format version, 27 and 28 are supported
+    pub version: u8,
+    pub universe: u32,
+    pub packages: Vec<PackageInfoEntry>
+}
+
+/// A single package within the packageinfo.vdf
+#[derive(Debug, Clone)]
+pub struct PackageInfoEntry {
+    pub package_id: u32,
+    pub sha1: [u8; 20],
+    pub change_number: u32,
+    /// Only present from version 28 onwards
+    pub pics_token: Option<u64>,
+    pub data: VdfStruct
+}
+
+/// Reads the complete packageinfo.vdf at the given location (usually `<steamroot>/appcache/packageinfo.vdf`)
+pub fn read_packageinfo_file(file_path: &Path) -> Option<PackageInfo> {
+    let data = fs::read(file_path).ok()?;
+    let mut reader = Reader { data: &data, pos: 0 };
+
+    let version = match reader.u32()? {
+        PACKAGEINFO_MAGIC_27 => 27,
+        PACKAGEINFO_MAGIC_28 => 28,
+        _ => return None
+    };
+    let universe = reader.u32()?;
+
+    let mut packages = Vec::new();
+    loop {
+        let package_id = reader.u32()?;
+        if package_id == u32::MAX {
+            break;
+        }
+
+        let sha1 = reader.sha1()?;
+        let change_number = reader.u32()?;
+        let pics_token = if version >= 28 {
+            Some(reader.u64()?)
+        } else {
+            None
+        };
+        let data = parse_section(&mut reader, None, false)?;
+
+        packages.push(PackageInfoEntry { package_id, sha1, change_number, pics_token, data });
+    }
+
+    Some(PackageInfo { version, universe, packages })
+}
+
+/// Parses pairs till the end of the section.
+/// When this is the root running out of data is also accepted as the end
+fn parse_section(reader: &mut Reader, strings: Option<&[String]>, root: bool) -> Option<VdfStruct> {
+    let mut obj = VdfStruct::new();
+
+    loop {
+        let kind = match reader.u8() {
+            Some(kind) => kind,
+            None if root => return Some(obj),
+            None => return None
+        };
+
+        if kind == TYPE_END || kind == TYPE_END_ALT {
+            return Some(obj);
+        }
+
+        let key = match strings {
+            Some(strings) => strings.get(reader.u32()? as usize)?.clone(),
+            None => reader.cstr()?
+        };
+
+        let value = match kind {
+            TYPE_SECTION => VdfValue::Complex(parse_section(reader, strings, false)?),
+            TYPE_STRING => VdfValue::Simple(reader.cstr()?),
+            TYPE_INT32 => VdfValue::Int32(reader.u32()? as i32),
+            TYPE_FLOAT => VdfValue::Float(f32::from_bits(reader.u32()?)),
+            TYPE_POINTER => VdfValue::Pointer(reader.u32()?),
+            TYPE_WIDE_STRING => VdfValue::Simple(reader.wide_cstr()?),
+            TYPE_COLOR => VdfValue::Color(reader.bytes::<4>()?),
+            TYPE_UINT64 => VdfValue::UInt64(reader.u64()?),
+            TYPE_INT64 => VdfValue::Int64(reader.u64()? as i64),
+            _ => return None
+        };
+
+        obj.push(key, value);
+    }
+}
+
+/// Reads little endian values from a byte slice
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize
+}
+
+impl Reader<'_> {
+    fn bytes<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let end = self.pos.checked_add(N)?;
+        let res = self.data.get(self.pos..end)?.try_into().ok()?;
+        self.pos = end;
+        Some(res)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.bytes::<1>()?[0])
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.bytes()?))
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        Some(u64::from_le_bytes(self.bytes()?))
+    }
+
+    fn sha1(&mut self) -> Option<[u8; 20]> {
+        self.bytes()
+    }
+
+    /// Null terminated utf8 string
+    fn cstr(&mut self) -> Option<String> {
+        let rest = self.data.get(self.pos..)?;
+        let len = rest.iter().position(|b| *b == 0)?;
+        let res = String::from_utf8_lossy(&rest[..len]).into_owned();
+        self.pos += len + 1;
+        Some(res)
+    }
+
+    /// Null terminated utf16 (little endian) string
+    fn wide_cstr(&mut self) -> Option<String> {
+        let mut units = Vec::new();
+        loop {
+            let unit = u16::from_le_bytes(self.bytes()?);
+            if unit == 0 {
+                break;
+            }
+            units.push(unit);
+        }
+
+        Some(String::from_utf16_lossy(&units))
+    }
+}
@@ -0,0 +1,319 @@
+use std::{fmt::Display, path::Path};
+
+use serde::de::{self, DeserializeOwned, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor};
+
+use super::{parse_vdf_file, parse_vdf_str, VdfStruct, VdfValue};
+
+/// Error produced while (de)serializing vdf with serde
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    msg: String
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.msg)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error { msg: msg.to_string() }
+    }
+}
+
+impl serde::ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error { msg: msg.to_string() }
+    }
+}
+
+impl Error {
+    pub(super) fn new(msg: &str) -> Self {
+        Error { msg: msg.to_string() }
+    }
+}
+
+/// Deserializes vdf text into the given type
+///
+/// The root of the file is treated as a struct, so for example libraryfolders.vdf needs a type with
+/// a `libraryfolders` field (see `linux::models`).
+pub fn from_str<T: DeserializeOwned>(text: &str) -> Result<T, Error> {
+    from_struct(parse_vdf_str(text).ok_or_else(|| Error::new("failed to parse vdf"))?)
+}
+
+/// Deserializes the vdf file at the given location into the given type, same as `from_str`
+pub fn from_file<T: DeserializeOwned>(file_path: &Path) -> Result<T, Error> {
+    from_struct(parse_vdf_file(file_path).ok_or_else(|| Error::new("failed to read or parse vdf file"))?)
+}
+
+/// Deserializes an already parsed vdf into the given type
+///
+/// Simpletypes are parsed into numbers and bools (`"0"`/`"1"`) as requested by the type,
+/// Complextypes can be read as structs, maps and sequences (for sequences the keys are ignored,
+/// like the `"0"`, `"1"`... keys steam uses for lists).
+/// For duplicate keys the last pair wins, same as the lookups on `VdfStruct`.
+pub fn from_struct<T: DeserializeOwned>(vdf: VdfStruct) -> Result<T, Error> {
+    T::deserialize(ValueDeserializer(VdfValue::Complex(vdf)))
+}
+
+struct ValueDeserializer(VdfValue);
+
+impl ValueDeserializer {
+    fn text(&self) -> Result<String, Error> {
+        self.0.to_text().ok_or_else(|| Error::new("expected a value, found a struct"))
+    }
+
+    fn parse<T: std::str::FromStr>(&self) -> Result<T, Error> {
+        let text = self.text()?;
+        text.trim().parse().map_err(|_| <Error as de::Error>::custom(format!("unable to parse number from \"{}\"", text)))
+    }
+}
+
+macro_rules! deserialize_number {
+    ($($fn:ident => $visit:ident),*) => {
+        $(
+            fn $fn<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                visitor.$visit(self.parse()?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            VdfValue::Complex(obj) => visitor.visit_map(StructAccess::new(obj)),
+            VdfValue::Simple(val) => visitor.visit_string(val),
+            VdfValue::Int32(val) => visitor.visit_i32(val),
+            VdfValue::UInt64(val) => visitor.visit_u64(val),
+            VdfValue::Int64(val) => visitor.visit_i64(val),
+            VdfValue::Float(val) => visitor.visit_f32(val),
+            VdfValue::Color(val) => visitor.visit_seq(de::value::SeqDeserializer::<_, Error>::new(val.into_iter())),
+            VdfValue::Pointer(val) => visitor.visit_u32(val)
+        }
+    }
+
+    deserialize_number!(
+        deserialize_i8 => visit_i8, deserialize_i16 => visit_i16, deserialize_i32 => visit_i32, deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8, deserialize_u16 => visit_u16, deserialize_u32 => visit_u32, deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32, deserialize_f64 => visit_f64
+    );
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let text = self.text()?;
+        match text.trim() {
+            "1" => visitor.visit_bool(true),
+            "0" | "" => visitor.visit_bool(false),
+            t if t.eq_ignore_ascii_case("true") => visitor.visit_bool(true),
+            t if t.eq_ignore_ascii_case("false") => visitor.visit_bool(false),
+            _ => Err(<Error as de::Error>::custom(format!("unable to parse bool from \"{}\"", text)))
+        }
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let text = self.text()?;
+        let mut chars = text.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => visitor.visit_char(c),
+            _ => Err(<Error as de::Error>::custom(format!("expected a single character, found \"{}\"", text)))
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_string(self.text()?)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_string(self.text()?)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_byte_buf(self.text()?.into_bytes())
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_byte_buf(self.text()?.into_bytes())
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        // A missing key is already a None, so whatever is present is Some
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            VdfValue::Complex(obj) => visitor.visit_seq(ListAccess { iter: obj.pairs.into_iter() }),
+            VdfValue::Color(val) => visitor.visit_seq(de::value::SeqDeserializer::<_, Error>::new(val.into_iter())),
+            _ => Err(Error::new("expected a struct for a sequence, found a value"))
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(self, _name: &'static str, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            VdfValue::Complex(obj) => visitor.visit_map(StructAccess::new(obj)),
+            _ => Err(Error::new("expected a struct, found a value"))
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(self, _name: &'static str, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            // Externally tagged, so a struct with a single key (the variant)
+            VdfValue::Complex(mut obj) => {
+                if obj.len() != 1 {
+                    return Err(Error::new("expected a struct with a single key for an enum"));
+                }
+
+                let (variant, value) = obj.pairs.remove(0);
+                visitor.visit_enum(EnumDeserializer { variant, value: Some(value) })
+            },
+            value => visitor.visit_enum(EnumDeserializer { variant: ValueDeserializer(value).text()?, value: None })
+        }
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_string(self.text()?)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+}
+
+/// Access to the pairs of a struct as a map
+struct StructAccess {
+    iter: std::vec::IntoIter<(String, VdfValue)>,
+    value: Option<VdfValue>
+}
+
+impl StructAccess {
+    /// Derived structs reject duplicate fields, so only the last value of each key is passed on
+    fn new(obj: VdfStruct) -> Self {
+        let mut pairs: Vec<(String, VdfValue)> = Vec::with_capacity(obj.pairs.len());
+        for (key, value) in obj.pairs {
+            match pairs.iter_mut().find(|(other, _)| *other == key) {
+                Some(pair) => pair.1 = value,
+                None => pairs.push((key, value))
+            }
+        }
+
+        StructAccess { iter: pairs.into_iter(), value: None }
+    }
+}
+
+impl<'de> MapAccess<'de> for StructAccess {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Error> {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(ValueDeserializer(VdfValue::Simple(key))).map(Some)
+            },
+            None => Ok(None)
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        match self.value.take() {
+            Some(value) => seed.deserialize(ValueDeserializer(value)),
+            None => Err(Error::new("value requested before key"))
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+/// Access to the values of a struct as a sequence
+struct ListAccess {
+    iter: std::vec::IntoIter<(String, VdfValue)>
+}
+
+impl<'de> SeqAccess<'de> for ListAccess {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, Error> {
+        match self.iter.next() {
+            Some((_, value)) => seed.deserialize(ValueDeserializer(value)).map(Some),
+            None => Ok(None)
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct EnumDeserializer {
+    variant: String,
+    value: Option<VdfValue>
+}
+
+impl<'de> EnumAccess<'de> for EnumDeserializer {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), Error> {
+        let variant = seed.deserialize(ValueDeserializer(VdfValue::Simple(self.variant.clone())))?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for EnumDeserializer {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        match self.value {
+            Some(value) => seed.deserialize(ValueDeserializer(value)),
+            None => Err(Error::new("expected a struct for a newtype variant"))
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Some(value) => de::Deserializer::deserialize_seq(ValueDeserializer(value), visitor),
+            None => Err(Error::new("expected a struct for a tuple variant"))
+        }
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            Some(value) => de::Deserializer::deserialize_map(ValueDeserializer(value), visitor),
+            None => Err(Error::new("expected a struct for a struct variant"))
+        }
+    }
+}
@@ -0,0 +1,315 @@
+use serde::ser::{self, Serialize};
+
+use super::{de::Error, VdfStruct, VdfValue};
+
+/// Serializes the value into vdf text, formated like `VdfStruct::to_vdf_string`
+///
+/// The value has to serialize into a struct (or map), as this is the root of the file.
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> Result<String, Error> {
+    Ok(to_struct(value)?.to_vdf_string())
+}
+
+/// Serializes the value into a `VdfStruct`
+///
+/// As text vdf has no types, numbers are written as text and bools as `"1"`/`"0"` (like steam does).
+/// Sequences become structs with the keys `"0"`, `"1"`... and None values are left out.
+pub fn to_struct<T: Serialize + ?Sized>(value: &T) -> Result<VdfStruct, Error> {
+    match value.serialize(ValueSerializer)? {
+        Some(VdfValue::Complex(obj)) => Ok(obj),
+        _ => Err(Error::new("the root has to be a struct or map"))
+    }
+}
+
+/// Produces a value, or None if the value should be left out
+struct ValueSerializer;
+
+fn simple(text: impl ToString) -> Result<Option<VdfValue>, Error> {
+    Ok(Some(VdfValue::Simple(text.to_string())))
+}
+
+/// Wraps the value into a struct with the variant as the only key (externally tagged)
+fn tagged(variant: &'static str, value: VdfValue) -> VdfValue {
+    let mut obj = VdfStruct::new();
+    obj.push(variant, value);
+    VdfValue::Complex(obj)
+}
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Option<VdfValue>;
+    type Error = Error;
+
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = SeqSerializer;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = StructSerializer;
+    type SerializeStructVariant = StructSerializer;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Error> {
+        simple(if v { "1" } else { "0" })
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Error> {
+        simple(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Error> {
+        simple(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Error> {
+        simple(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Error> {
+        simple(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Error> {
+        simple(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Error> {
+        simple(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Error> {
+        simple(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Error> {
+        simple(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Error> {
+        simple(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Error> {
+        simple(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Error> {
+        simple(v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Error> {
+        simple(v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Error> {
+        simple(String::from_utf8_lossy(v))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Error> {
+        Ok(None)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Self::Ok, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Error> {
+        simple("")
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Error> {
+        simple("")
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str) -> Result<Self::Ok, Error> {
+        simple(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<Self::Ok, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _variant_index: u32, variant: &'static str, value: &T) -> Result<Self::Ok, Error> {
+        Ok(value.serialize(self)?.map(|value| tagged(variant, value)))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Ok(SeqSerializer { obj: VdfStruct::new(), variant: None })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeTupleStruct, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str, _len: usize) -> Result<Self::SerializeTupleVariant, Error> {
+        Ok(SeqSerializer { obj: VdfStruct::new(), variant: Some(variant) })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Ok(MapSerializer { obj: VdfStruct::new(), key: None })
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct, Error> {
+        Ok(StructSerializer { obj: VdfStruct::new(), variant: None })
+    }
+
+    fn serialize_struct_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str, _len: usize) -> Result<Self::SerializeStructVariant, Error> {
+        Ok(StructSerializer { obj: VdfStruct::new(), variant: Some(variant) })
+    }
+}
+
+struct SeqSerializer {
+    obj: VdfStruct,
+    variant: Option<&'static str>
+}
+
+impl SeqSerializer {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let index = self.obj.len().to_string();
+        if let Some(value) = value.serialize(ValueSerializer)? {
+            self.obj.push(index, value);
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Option<VdfValue>, Error> {
+        let value = VdfValue::Complex(self.obj);
+        Ok(Some(match self.variant {
+            Some(variant) => tagged(variant, value),
+            None => value
+        }))
+    }
+}
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = Option<VdfValue>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = Option<VdfValue>;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = Option<VdfValue>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SeqSerializer {
+    type Ok = Option<VdfValue>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        self.finish()
+    }
+}
+
+struct MapSerializer {
+    obj: VdfStruct,
+    key: Option<String>
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = Option<VdfValue>;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        match key.serialize(ValueSerializer)? {
+            Some(VdfValue::Simple(key)) => {
+                self.key = Some(key);
+                Ok(())
+            },
+            _ => Err(Error::new("map keys have to be strings or numbers"))
+        }
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self.key.take().ok_or_else(|| Error::new("value serialized before key"))?;
+        if let Some(value) = value.serialize(ValueSerializer)? {
+            self.obj.push(key, value);
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        Ok(Some(VdfValue::Complex(self.obj)))
+    }
+}
+
+struct StructSerializer {
+    obj: VdfStruct,
+    variant: Option<&'static str>
+}
+
+impl StructSerializer {
+    fn push<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Error> {
+        if let Some(value) = value.serialize(ValueSerializer)? {
+            self.obj.push(key, value);
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Option<VdfValue>, Error> {
+        let value = VdfValue::Complex(self.obj);
+        Ok(Some(match self.variant {
+            Some(variant) => tagged(variant, value),
+            None => value
+        }))
+    }
+}
+
+impl ser::SerializeStruct for StructSerializer {
+    type Ok = Option<VdfValue>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Error> {
+        self.push(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for StructSerializer {
+    type Ok = Option<VdfValue>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Error> {
+        self.push(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        self.finish()
+    }
+}
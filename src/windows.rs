@@ -0,0 +1,95 @@
+use std::{ffi::{c_void, OsString}, os::windows::ffi::OsStringExt, path::PathBuf};
+
+use windows_sys::{core::{GUID, PWSTR}, Win32::{System::Com::CoTaskMemFree, UI::Shell::{SHGetKnownFolderPath, KF_FLAG_CREATE}}};
+use winreg::{enums::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE}, RegKey};
+
+use crate::{vdf::{parse_vdf_file, VdfValue}, KnownFolder};
+
+/// Returns the steam install folder, as noted in the registry
+pub(crate) fn steam_root() -> Option<PathBuf> {
+    if let Ok(key) = RegKey::predef(HKEY_CURRENT_USER).open_subkey("Software\\Valve\\Steam") {
+        if let Ok(path) = key.get_value::<String, _>("SteamPath") {
+            return Some(PathBuf::from(path));
+        }
+    }
+
+    // Written by the installer, so this is present even if steam was never launched by this user
+    let key = RegKey::predef(HKEY_LOCAL_MACHINE).open_subkey("SOFTWARE\\WOW6432Node\\Valve\\Steam").ok()?;
+    key.get_value::<String, _>("InstallPath").ok().map(PathBuf::from)
+}
+
+/// Returns all steam library folders, starting with the steam root
+pub(crate) fn steam_libraries() -> Vec<PathBuf> {
+    let mut res = Vec::<PathBuf>::new();
+    let root = match steam_root() {
+        Some(root) => root,
+        None => return res
+    };
+
+    let mut vdf_path = root.clone();
+    vdf_path.push("steamapps");
+    vdf_path.push("libraryfolders.vdf");
+    res.push(root);
+
+    if let Some(vdf) = parse_vdf_file(&vdf_path) {
+        if let Ok(libs) = vdf.get_path("libraryfolders").and_then(VdfValue::as_struct) {
+            for (_, lib) in libs.iter() {
+                if let Ok(path) = lib.as_struct().and_then(|lib| lib.get_path("path")).and_then(VdfValue::as_str) {
+                    let path = PathBuf::from(path);
+                    if !res.contains(&path) {
+                        res.push(path);
+                    }
+                }
+            }
+        }
+    }
+
+    res
+}
+
+/// Returns the folder the game is installed in, by reading the appmanifest from every library
+pub(crate) fn install_dir(game_id: u32) -> Option<PathBuf> {
+    for lib in steam_libraries() {
+        let mut steamapps = lib;
+        steamapps.push("steamapps");
+
+        let mut manifest = steamapps.clone();
+        manifest.push(format!("appmanifest_{}.acf", game_id));
+
+        if let Some(vdf) = parse_vdf_file(&manifest) {
+            if let Ok(dir) = vdf.get_path("AppState/installdir").and_then(VdfValue::as_str) {
+                // NTFS ignores case, so no need to search the folder
+                let mut path = steamapps;
+                path.push("common");
+                path.push(dir);
+
+                if path.is_dir() {
+                    return Some(path);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns the path of the known folder through `SHGetKnownFolderPath`, optionally creating it
+pub(crate) fn known_folder(folder: KnownFolder, create: bool) -> Option<PathBuf> {
+    let guid = folder.guid().trim_start_matches('{').trim_end_matches('}').replace('-', "");
+    let guid = GUID::from_u128(u128::from_str_radix(&guid, 16).ok()?);
+
+    let mut ptr: PWSTR = std::ptr::null_mut();
+    // SAFETY: the returned string is null terminated, and has to be freed even if the call fails
+    unsafe {
+        let res = SHGetKnownFolderPath(&guid, if create { KF_FLAG_CREATE } else { 0 }, 0, &mut ptr);
+        let path = if res >= 0 && !ptr.is_null() {
+            let len = (0..).take_while(|&i| *ptr.add(i) != 0).count();
+            Some(PathBuf::from(OsString::from_wide(std::slice::from_raw_parts(ptr, len))))
+        } else {
+            None
+        };
+        CoTaskMemFree(ptr as *const c_void);
+
+        path
+    }
+}